@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rustassert::assert::{self, SimpleMatcher};
+use rustassert::{check, fn_matcher};
+
+#[derive(Debug, PartialEq)]
+struct Record {
+    id: u32,
+    name: String,
+    tags: Vec<String>,
+}
+
+fn record(id: u32) -> Record {
+    Record {
+        id,
+        name: format!("record {}", id),
+        tags: vec![String::from("a"), String::from("b")],
+    }
+}
+
+fn passing_assertions(c: &mut Criterion) {
+    let mut assert = assert::new();
+    let values: Vec<u32> = (0..100).collect();
+    let r = record(1);
+    let expected = record(1);
+
+    c.bench_function("is_eq u32", |b| {
+        b.iter(|| {
+            assert.that(black_box(13)).is_eq(13);
+        })
+    });
+    c.bench_function("not is_eq u32", |b| {
+        b.iter(|| {
+            assert.that(black_box(13)).not().is_eq(14);
+        })
+    });
+    c.bench_function("is_eq struct", |b| {
+        b.iter(|| {
+            assert.that_ref(black_box(&r)).is_eq(&expected);
+        })
+    });
+    c.bench_function("has_len", |b| {
+        b.iter(|| {
+            assert.that_ref(black_box(&values)).has_len(100);
+        })
+    });
+    c.bench_function("contains", |b| {
+        b.iter(|| {
+            assert.that_ref(black_box(&values)).contains(50);
+        })
+    });
+    c.bench_function("all", |b| {
+        b.iter(|| {
+            assert.that_ref(black_box(&values)).all(fn_matcher!(&|a| *a < 100));
+        })
+    });
+    c.bench_function("check!", |b| b.iter(|| check!(assert, black_box(values.len()) == 100)));
+}
+
+criterion_group!(benches, passing_assertions);
+criterion_main!(benches);
@@ -1,7 +1,8 @@
+use crate::assert::{DebugValue, EqExpectation, Equivalences};
 use backtrace::BacktraceFrame;
-use std::borrow::Borrow;
 use std::cell::RefCell;
-use std::fmt::Debug;
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
 use std::panic::panic_any;
 use std::rc::Rc;
 use std::sync::Once;
@@ -15,10 +16,13 @@ pub struct FailResult {
 pub(super) struct AssertionRef {
     failures: Vec<Box<dyn Fn()>>,
     f_handler: &'static dyn Fn(FailResult) -> Box<dyn Fn()>,
+    pub(super) equivalences: Equivalences,
+    name: Option<String>,
+    contexts: Vec<String>,
 }
 
 pub struct Assertion {
-    rca: Rc<RefCell<AssertionRef>>,
+    pub(super) rca: Rc<RefCell<AssertionRef>>,
 }
 
 fn default_f_handler(fr: FailResult) -> Box<dyn Fn()> {
@@ -35,61 +39,74 @@ pub fn new() -> Assertion {
 }
 
 pub fn new_with_handler(handler: &'static dyn Fn(FailResult) -> Box<dyn Fn()>) -> Assertion {
-    let rca = Rc::new(RefCell::new(AssertionRef { failures: vec![], f_handler: handler }));
+    let rca = Rc::new(RefCell::new(AssertionRef {
+        failures: vec![],
+        f_handler: handler,
+        equivalences: Equivalences::default(),
+        name: None,
+        contexts: vec![],
+    }));
     Assertion { rca }
 }
 
-pub fn that<A>(actual: A) -> Instance<A> {
+pub fn that<'a, A: 'a>(actual: A) -> Instance<'a, A> {
     new().that(actual)
 }
 
-static MODULE_PATH: &[u8] = module_path!().as_bytes();
+static MODULE_PATH: &str = module_path!();
+
+fn symbol_in_assert_module(name: &str) -> bool {
+    let module = &MODULE_PATH[..MODULE_PATH.rfind("::").unwrap_or(MODULE_PATH.len())];
+    let name = name.trim_start_matches('<');
+    name.starts_with(module) && name[module.len()..].starts_with("::") && !name.contains("::tests::")
+}
 
 impl Assertion {
-    pub fn that<A>(&mut self, actual: A) -> Instance<A> {
+    pub fn that<'a, A: 'a>(&mut self, actual: A) -> Instance<'a, A> {
         Instance {
             parent: Rc::clone(&self.rca),
-            actual: Box::new(actual),
-            instance_config: InstanceConfig {
-                negation: false,
-                panic_immediately: false,
-                backtrace: false,
-            },
+            actual: Actual::Owned(Box::new(actual)),
+            instance_config: InstanceConfig::default(),
+        }
+    }
+
+    pub fn that_ref<'a, A: ?Sized>(&mut self, actual: &'a A) -> Instance<'a, A> {
+        Instance {
+            parent: Rc::clone(&self.rca),
+            actual: Actual::Borrowed(actual),
+            instance_config: InstanceConfig::default(),
         }
     }
 
-    fn current_path() -> Vec<u8> {
-        MODULE_PATH.iter().chain("::".as_bytes().iter()).cloned().collect()
+    pub fn named(&mut self, name: &str) -> &mut Self {
+        self.rca.borrow_mut().name = Some(name.to_string());
+        self
     }
 
-    fn frame_in_assertion_module(frame: &BacktraceFrame) -> bool {
-        let cp = Assertion::current_path();
-        for s in frame.symbols() {
-            if let Some(name) = s.name() {
-                if name.as_bytes().starts_with(cp.as_slice()) {
-                    return true;
-                }
-            }
+    pub fn context<R, F: FnOnce(&mut Self) -> R>(&mut self, label: &str, f: F) -> R {
+        self.rca.borrow_mut().contexts.push(label.to_string());
+        let _guard = ContextGuard(Rc::clone(&self.rca));
+        f(self)
+    }
+
+    pub fn that_expression<'a, A: 'a>(&mut self, actual: A, expression: &str) -> Instance<'a, A> {
+        let mut instance = self.that(actual);
+        instance.instance_config.expression = Some(expression.to_string());
+        instance
+    }
+
+    pub fn check<L: FnOnce() -> String>(&mut self, ok: bool, log: L) {
+        if !ok {
+            self.rca.borrow_mut().fail(&InstanceConfig::default(), log());
         }
-        false
+    }
+
+    fn frame_in_assertion_module(frame: &BacktraceFrame) -> bool {
+        frame.symbols().iter().any(|s| matches!(s.name(), Some(name) if symbol_in_assert_module(&format!("{:#}", name))))
     }
 
     fn backtrace_ignoring_current_mod(bt: backtrace::Backtrace) -> Vec<BacktraceFrame> {
-        return bt
-            .frames()
-            .iter()
-            .filter(|f| {
-                for s in f.symbols() {
-                    if let Some(name) = s.name() {
-                        if name.as_bytes().starts_with(Assertion::current_path().as_slice()) {
-                            return false;
-                        }
-                    }
-                }
-                true
-            })
-            .cloned()
-            .collect();
+        bt.frames().iter().filter(|f| !Assertion::frame_in_assertion_module(f)).cloned().collect()
     }
 
     fn failed_line_bt(bt: backtrace::Backtrace) -> Vec<BacktraceFrame> {
@@ -97,7 +114,7 @@ impl Assertion {
         let mut last_f_index = 0;
         let f = bt.frames();
         for (i, f) in f.iter().enumerate() {
-            if Assertion::frame_in_assertion_module(&f) {
+            if Assertion::frame_in_assertion_module(f) {
                 index_found = true;
                 last_f_index = i;
             }
@@ -107,28 +124,41 @@ impl Assertion {
             return vec![];
         }
 
-        return vec![f[last_f_index + 1].clone()];
+        vec![f[last_f_index + 1].clone()]
     }
 }
 
 impl AssertionRef {
+    fn breadcrumbs<'a>(&'a self, instance_config: &'a InstanceConfig) -> Vec<&'a str> {
+        self.name.iter().chain(self.contexts.iter()).chain(instance_config.description.iter()).map(String::as_str).collect()
+    }
+
     fn fail(&mut self, instance_config: &InstanceConfig, log: String) {
+        let breadcrumbs = self.breadcrumbs(instance_config);
+        let log = if breadcrumbs.is_empty() { log } else { format!("{}: {}", breadcrumbs.join(" > "), log) };
         if instance_config.panic_immediately {
             panic!("{}", log);
         }
-        let bt: backtrace::Backtrace;
-
-        if instance_config.backtrace {
-            bt = backtrace::Backtrace::from(Assertion::backtrace_ignoring_current_mod(backtrace::Backtrace::new()));
+        let bt = if instance_config.backtrace {
+            backtrace::Backtrace::from(Assertion::backtrace_ignoring_current_mod(backtrace::Backtrace::new()))
         } else {
-            bt = backtrace::Backtrace::from(Assertion::failed_line_bt(backtrace::Backtrace::new()));
-        }
+            backtrace::Backtrace::from(Assertion::failed_line_bt(backtrace::Backtrace::new()))
+        };
 
         let f_handler = self.f_handler;
         self.failures.push(f_handler(FailResult { log, bt }))
     }
 }
 
+// Pops the context label even when the closure panics, e.g. in a `catch_unwind`.
+struct ContextGuard(Rc<RefCell<AssertionRef>>);
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        self.0.borrow_mut().contexts.pop();
+    }
+}
+
 pub struct IgnorePanic();
 
 static INIT_TAKE_HOOK: Once = Once::new();
@@ -158,26 +188,86 @@ impl Drop for AssertionRef {
     }
 }
 
-pub struct Instance<A: ?Sized> {
+pub struct Instance<'a, A: ?Sized> {
     pub(super) parent: Rc<RefCell<AssertionRef>>,
-    pub(super) actual: Box<A>,
+    pub(super) actual: Actual<'a, A>,
     pub(super) instance_config: InstanceConfig,
 }
 
-#[derive(Clone)]
+pub(super) enum Actual<'a, A: ?Sized> {
+    Owned(Box<A>),
+    Borrowed(&'a A),
+    // Left by an accessor that found nothing to assert on, its failure is already recorded and later assertions are skipped.
+    Missing,
+}
+
+impl<A: ?Sized> Actual<'_, A> {
+    pub(super) fn is_missing(&self) -> bool {
+        matches!(self, Actual::Missing)
+    }
+}
+
+impl<A: ?Sized> Deref for Actual<'_, A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        match self {
+            Actual::Owned(a) => a,
+            Actual::Borrowed(a) => a,
+            Actual::Missing => panic!("missing actual is never asserted on"),
+        }
+    }
+}
+
+impl<A: ?Sized> AsRef<A> for Actual<'_, A> {
+    fn as_ref(&self) -> &A {
+        self
+    }
+}
+
+impl<A: Debug + ?Sized> Debug for Actual<'_, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Actual::Missing => f.write_str("<missing>"),
+            _ => self.deref().fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 pub(super) struct InstanceConfig {
     pub(super) negation: bool,
     panic_immediately: bool,
     backtrace: bool,
+    expression: Option<String>,
+    description: Option<String>,
+    projections: Vec<String>,
+}
+
+impl InstanceConfig {
+    fn labelled(&self, log: String) -> String {
+        let log = match &self.expression {
+            Some(expression) => format!("{}\n expression: `{}`", log, expression),
+            None => log,
+        };
+        if self.projections.is_empty() {
+            return log;
+        }
+        format!("{}\n projection: `actual{}`", log, self.projections.concat())
+    }
 }
 
-pub(super) struct Execution {
+pub(super) struct Execution<L, N>
+where
+    L: FnOnce() -> String,
+    N: FnOnce() -> String,
+{
     pub(super) ok: bool,
-    pub(super) log: String,
-    pub(super) nlog: String,
+    pub(super) log: L,
+    pub(super) nlog: N,
 }
 
-impl<A> Instance<A>
+impl<A> Instance<'_, A>
 where
     A: ?Sized,
 {
@@ -196,52 +286,148 @@ where
         self
     }
 
-    pub(super) fn handle_execution(&mut self, e: Execution) {
+    pub fn described_as(&mut self, description: &str) -> &mut Self {
+        self.instance_config.description = Some(description.to_string());
+        self
+    }
+
+    pub(super) fn finish(&mut self) -> &mut Self {
+        self.instance_config.negation = false;
+        self
+    }
+
+    pub(super) fn handle_execution<L, N>(&self, e: Execution<L, N>)
+    where
+        L: FnOnce() -> String,
+        N: FnOnce() -> String,
+    {
         if !e.ok && !self.instance_config.negation {
-            self.parent.borrow_mut().fail(&self.instance_config, e.log);
+            self.handle_failure(e.log);
         }
 
         if e.ok && self.instance_config.negation {
-            self.parent.borrow_mut().fail(&self.instance_config, e.nlog);
+            self.handle_failure(e.nlog);
+        }
+    }
+
+    pub(super) fn handle_failure<L: FnOnce() -> String>(&self, log: L) {
+        self.parent.borrow_mut().fail(&self.instance_config, self.instance_config.labelled(log()));
+    }
+
+    pub(super) fn projected<'b, E: ?Sized>(&self, projection: &str, actual: Actual<'b, E>) -> Instance<'b, E> {
+        let mut instance_config = self.instance_config.clone();
+        instance_config.projections.push(format!(".{}(..)", projection));
+        Instance {
+            parent: Rc::clone(&self.parent),
+            actual,
+            instance_config,
+        }
+    }
+
+    pub fn transform<'b, E: ?Sized + 'b, T: FnMut(&A) -> Box<E>>(&mut self, mut transformer: T) -> Instance<'b, E> {
+        Instance {
+            parent: Rc::clone(&self.parent),
+            actual: match self.actual {
+                Actual::Missing => Actual::Missing,
+                _ => Actual::Owned(transformer(&self.actual)),
+            },
+            instance_config: self.instance_config.clone(),
         }
     }
 
-    pub fn transform<E: ?Sized, T: FnMut(&A) -> Box<E>>(&mut self, mut transformer: T) -> Instance<E> {
+    pub fn project<E: ?Sized, T: FnOnce(&A) -> &E>(&self, projection: T) -> Instance<'_, E> {
         Instance {
             parent: Rc::clone(&self.parent),
-            actual: transformer(&self.actual),
+            actual: match self.actual {
+                Actual::Missing => Actual::Missing,
+                _ => Actual::Borrowed(projection(&self.actual)),
+            },
             instance_config: self.instance_config.clone(),
         }
     }
 }
 
-impl<A> Instance<A>
+impl<A> Instance<'_, A>
 where
-    A: Debug + PartialEq + ?Sized,
+    A: Debug + ?Sized,
 {
-    pub fn is_eq<E>(&mut self, expected: E)
+    pub fn is_eq<E, M>(&mut self, expected: E) -> &mut Self
     where
-        E: Borrow<A> + Debug,
+        E: EqExpectation<A, M> + Debug,
     {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
         let a = self.actual.as_ref();
-        let b = expected.borrow();
-        let ok = a.eq(b);
+        let equivalence = expected.equivalence(&RefCell::borrow(&self.parent).equivalences);
+        let ok = match &equivalence {
+            Some((c, e)) => c.compare(a, e),
+            None => expected.eq_actual(a),
+        };
+        let by = || equivalence.as_ref().map(|(c, _)| format!(" by `{}`", c.get_name())).unwrap_or_default();
         self.handle_execution(Execution {
             ok,
-            log: format!(
-                r#"assertion failed: `(actual == expectation)`
+            log: || match equivalence.as_ref().map_or_else(|| path_mismatches(a, &expected), |_| None) {
+                Some(m) => format!("assertion failed: `(actual == expectation)`\n{}", m.join("\n")),
+                None => format!(
+                    r#"assertion failed: `(actual == expectation)`{}
+     actual: `{:?}`
+expectation: `{:?}`"#,
+                    by(),
+                    self.actual,
+                    expected
+                ),
+            },
+            nlog: || {
+                format!(
+                    r#"assertion failed: `(actual != expectation)`{}
      actual: `{:?}`
 expectation: `{:?}`"#,
-                self.actual, expected
-            ),
-            nlog: format!(
-                r#"assertion failed: `(actual != expectation)`
+                    by(),
+                    self.actual,
+                    expected
+                )
+            },
+        });
+        self.finish()
+    }
+
+    pub fn is_eq_ignoring<E, M>(&mut self, ignored: &[&str], expected: E) -> &mut Self
+    where
+        E: EqExpectation<A, M> + Debug,
+    {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let a = self.actual.as_ref();
+        let mismatches: Vec<String> = match (DebugValue::parse(&format!("{:#?}", a)), DebugValue::parse(&format!("{:#?}", expected))) {
+            (Some(a_tree), Some(e_tree)) => a_tree.diff_ignoring(&e_tree, ignored).iter().map(|m| m.to_string()).collect(),
+            _ if expected.eq_actual(a) => vec![],
+            _ if ignored.is_empty() => vec![format!("at .: {:?} != {:?}", a, expected)],
+            _ => vec![format!("at .: {:?} != {:?} (values could not be parsed, nothing was ignored)", a, expected)],
+        };
+        self.handle_execution(Execution {
+            ok: mismatches.is_empty(),
+            log: || format!("assertion failed: `(actual == expectation)` ignoring `{:?}`\n{}", ignored, mismatches.join("\n")),
+            nlog: || {
+                format!(
+                    r#"assertion failed: `(actual != expectation)` ignoring `{:?}`
      actual: `{:?}`
 expectation: `{:?}`"#,
-                self.actual, expected
-            ),
+                    ignored, self.actual, expected
+                )
+            },
         });
+        self.finish()
+    }
+}
+
+fn path_mismatches<A: Debug + ?Sized, E: Debug + ?Sized>(a: &A, e: &E) -> Option<Vec<String>> {
+    let mismatches = DebugValue::parse(&format!("{:#?}", a))?.diff(&DebugValue::parse(&format!("{:#?}", e))?);
+    if mismatches.iter().all(|m| m.path.is_empty()) {
+        return None;
     }
+    Some(mismatches.iter().map(|m| m.to_string()).collect())
 }
 
 pub trait MatcherTrait<A: ?Sized> {
@@ -270,11 +456,11 @@ impl<A> MatcherTrait<A> for SimpleMatcher<A> {
     }
 
     fn log_fn(&self, _: &A) -> String {
-        return format!("assertion failed: `(matcher {:?} failed)`", self.m_name);
+        format!("assertion failed: `(matcher {:?} failed)`", self.m_name)
     }
 
     fn nlog_fn(&self, _: &A) -> String {
-        return format!("assertion failed: `(matcher {:?} succeed while it shouldn't)`", self.m_name);
+        format!("assertion failed: `(matcher {:?} succeed while it shouldn't)`", self.m_name)
     }
 }
 
@@ -285,18 +471,24 @@ macro_rules! fn_matcher {
     };
 }
 
-impl<A> Instance<A>
+impl<A> Instance<'_, A>
 where
     A: ?Sized,
 {
-    pub fn do_match<M>(&mut self, matcher: M)
+    pub fn do_match<M>(&mut self, matcher: M) -> &mut Self
     where
         M: MatcherTrait<A>,
     {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
         let a = self.actual.as_ref();
         let ok = matcher.matcher_fn(a);
-        let log = matcher.log_fn(a);
-        let nlog = matcher.nlog_fn(a);
-        self.handle_execution(Execution { ok, log, nlog });
+        self.handle_execution(Execution {
+            ok,
+            log: || matcher.log_fn(a),
+            nlog: || matcher.nlog_fn(a),
+        });
+        self.finish()
     }
 }
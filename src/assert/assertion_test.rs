@@ -2,10 +2,10 @@
 mod tests {
     use crate::assert;
     use crate::assert::{FailResult, SimpleMatcher};
-    use crate::fn_matcher;
+    use crate::{fn_comparator, fn_matcher};
     use std::path::PathBuf;
     use std::{env, panic};
-    const BASE_FOLDER: &'static str = env!("CARGO_MANIFEST_DIR");
+    const BASE_FOLDER: &str = env!("CARGO_MANIFEST_DIR");
 
     #[macro_export]
     macro_rules! assert_panic_ignored {
@@ -21,6 +21,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn assert_eq_should_pass() {
         let mut assert = assert::new();
 
@@ -81,7 +82,7 @@ expectation: `"b"`"#
                             f: vec![FrameSum {
                                 v: vec![FrameSymSum {
                                     name: "rustassert::assert::assertion_test::tests::assert_eq_should_fail::{{closure}}".to_string(),
-                                    line: 92,
+                                    line: 93,
                                     file: PathBuf::from(BASE_FOLDER).join(file!()),
                                 }]
                             }]
@@ -110,7 +111,7 @@ expectation: `"b"`"#
                     assert!(bs.f.contains(&FrameSum {
                         v: vec![FrameSymSum {
                             name: "rustassert::assert::assertion_test::tests::assert_eq_should_fail_with_backtrace::{{closure}}".to_string(),
-                            line: 119,
+                            line: 120,
                             file: PathBuf::from(BASE_FOLDER).join(file!()),
                         }]
                     }));
@@ -138,7 +139,7 @@ expectation: `"a"`"#
                             f: vec![FrameSum {
                                 v: vec![FrameSymSum {
                                     name: "rustassert::assert::assertion_test::tests::assert_eq_should_fail_with_negation::{{closure}}".to_string(),
-                                    line: 149,
+                                    line: 150,
                                     file: PathBuf::from(BASE_FOLDER).join(file!()),
                                 }]
                             }]
@@ -163,7 +164,7 @@ expectation: `"a"`"#
         let result = panic::catch_unwind(|| {
             let mut assert = assert::new_with_handler(&|fr: FailResult| {
                 Box::new(move || {
-                    assert_eq!(fr.log, "assertion failed: `(matcher \"&(|a| *a == 2)\" failed)`");
+                    assert_eq!(fr.log, "assertion failed: `(matcher \"&|a| *a == 2\" failed)`");
                 })
             });
             assert.that(1).do_match(fn_matcher!(&|a| *a == 2));
@@ -176,7 +177,7 @@ expectation: `"a"`"#
         let result = panic::catch_unwind(|| {
             let mut assert = assert::new_with_handler(&|fr: FailResult| {
                 Box::new(move || {
-                    assert_eq!(fr.log, "assertion failed: `(matcher \"&(|a| *a == 2)\" succeed while it shouldn't)`");
+                    assert_eq!(fr.log, "assertion failed: `(matcher \"&|a| *a == 2\" succeed while it shouldn't)`");
                 })
             });
             assert.that(2).not().do_match(fn_matcher!(&|a| *a == 2));
@@ -184,6 +185,269 @@ expectation: `"a"`"#
         assert_panic_ignored!(result)
     }
 
+    #[derive(Debug, PartialEq)]
+    struct Record {
+        id: u32,
+        name: String,
+        meta: RecordMeta,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct RecordMeta {
+        created_at: u64,
+        version: u32,
+    }
+
+    fn record(id: u32, name: &str, created_at: u64, version: u32) -> Record {
+        Record {
+            id,
+            name: name.to_string(),
+            meta: RecordMeta { created_at, version },
+        }
+    }
+
+    #[test]
+    fn assert_eq_ignoring_should_pass() {
+        let mut assert = assert::new();
+        assert.that(record(1, "a", 10, 1)).is_eq_ignoring(&[], record(1, "a", 10, 1));
+        assert.that(record(1, "a", 10, 1)).is_eq_ignoring(&["id", "meta.created_at"], record(2, "a", 20, 1));
+        assert.that(record(1, "a", 10, 1)).is_eq_ignoring(&["id", "meta.*"], record(2, "a", 20, 2));
+        assert.that(record(1, "a", 10, 1)).not().is_eq_ignoring(&["id"], record(2, "a", 20, 1));
+        assert
+            .that(vec![record(1, "a", 10, 1), record(2, "b", 10, 1)])
+            .is_eq_ignoring(&["[*].id"], vec![record(3, "a", 10, 1), record(4, "b", 10, 1)]);
+    }
+
+    #[test]
+    fn assert_eq_ignoring_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)` ignoring `["id", "meta.created_at"]`
+at .name: "a" != "b"
+at .meta.version: 1 != 2"#
+                    );
+                })
+            });
+            assert.that(record(1, "a", 10, 1)).is_eq_ignoring(&["id", "meta.created_at"], record(2, "b", 20, 2));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[derive(PartialEq)]
+    struct Opaque(u8);
+
+    impl std::fmt::Debug for Opaque {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "<opaque {}>", self.0)
+        }
+    }
+
+    #[test]
+    fn assert_eq_ignoring_should_tell_when_ignore_list_cannot_be_applied() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)` ignoring `["id"]`
+at .: <opaque 1> != <opaque 2> (values could not be parsed, nothing was ignored)"#
+                    );
+                })
+            });
+            assert.that(Opaque(1)).is_eq_ignoring(&["id"], Opaque(1)).is_eq_ignoring(&["id"], Opaque(2));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_eq_ignoring_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual != expectation)` ignoring `["id"]`
+     actual: `Record { id: 1, name: "a", meta: RecordMeta { created_at: 10, version: 1 } }`
+expectation: `Record { id: 2, name: "a", meta: RecordMeta { created_at: 10, version: 1 } }`"#
+                    );
+                })
+            });
+            assert.that(record(1, "a", 10, 1)).not().is_eq_ignoring(&["id"], record(2, "a", 10, 1));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_eq_should_fail_with_mismatches_by_path() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+at [1].name: "b" != "c"
+at [1].meta.version: 1 != 2
+at [2]: <missing> != Record { id: 3, name: "c", meta: RecordMeta { created_at: 10, version: 1 } }"#
+                    );
+                })
+            });
+            assert
+                .that(vec![record(1, "a", 10, 1), record(2, "b", 10, 1)])
+                .is_eq(vec![record(1, "a", 10, 1), record(2, "c", 10, 2), record(3, "c", 10, 1)]);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_eq_should_pass_with_heterogeneous_expectations() {
+        let mut assert = assert::new();
+        assert.that(String::from("a")).is_eq("a");
+        assert.that(String::from("a")).not().is_eq("b");
+        assert.that(vec![1u8, 2]).is_eq(&[1u8, 2][..]);
+        assert.that(vec![1u8, 2]).is_eq([1u8, 2]);
+        assert.that(PathBuf::from("a/b")).is_eq("a/b");
+        assert.that(PathBuf::from("a/b")).not().is_eq("a/c");
+        assert.that(PathBuf::from("a/b")).is_eq(String::from("a/b"));
+        assert.that(String::from("a")).transform(|a| Box::<str>::from(a.as_str())).is_eq("a");
+    }
+
+    #[test]
+    fn assert_eq_should_fail_with_heterogeneous_expectation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+     actual: `"a/b"`
+expectation: `"a/c"`"#
+                    );
+                })
+            });
+            assert.that(PathBuf::from("a/b")).is_eq("a/c");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_should_fail_with_description_and_name() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"parse config > retry count after timeout: assertion failed: `(actual == expectation)`
+     actual: `2`
+expectation: `3`"#
+                    );
+                })
+            });
+            assert.named("parse config");
+            assert.that(2).described_as("retry count after timeout").is_eq(3);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_should_fail_with_context_breadcrumbs() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "case 3 > item: assertion failed: `(matcher \"&|a| *a == 2\" failed)` - at position 1");
+                })
+            });
+            assert.that(1).is_eq(1);
+            assert.context("case 3", |a| {
+                a.context("item", |a| {
+                    a.that(vec![2, 3]).all(fn_matcher!(&|a| *a == 2));
+                });
+                a.that(2).is_eq(2);
+            });
+            assert.that(1).is_eq(1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_should_pop_context_after_closure() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(actual == expectation)`\n     actual: `1`\nexpectation: `2`");
+                })
+            });
+            let passed = assert.context("outer", |a| {
+                a.that(vec![1]).eq_each(&[1]);
+                true
+            });
+            assert!(passed);
+            let unwound = panic::catch_unwind(panic::AssertUnwindSafe(|| assert.context("outer", |_| panic!("in context"))));
+            assert!(unwound.is_err());
+            assert.that(1).is_eq(2);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Fixture {
+        records: Vec<Record>,
+    }
+
+    #[test]
+    fn assert_should_pass_on_borrowed_values() {
+        let mut assert = assert::new();
+        let fixture = Fixture {
+            records: (0..1000).map(|i| record(i, "a", 10, 1)).collect(),
+        };
+        for i in 0..1000 {
+            assert.that_ref(&fixture.records[i as usize]).project(|r| &r.id).is_eq(i);
+        }
+        assert.that_ref(&fixture).project(|f| &f.records).has_len(1000);
+        assert.that_ref(&fixture).project(|f| &f.records[1].meta).is_eq(RecordMeta { created_at: 10, version: 1 });
+        assert.that_ref(&fixture.records[0].name).project(|n| n.as_str()).is_eq("a").not().is_eq("b");
+    }
+
+    #[test]
+    fn assert_should_fail_on_borrowed_values() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+     actual: `"a"`
+expectation: `"b"`"#
+                    );
+                })
+            });
+            let r = record(1, "a", 10, 1);
+            assert.that_ref(&r).project(|r| r.name.as_str()).is_eq("b");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[derive(PartialEq)]
+    struct NoDebugOnPass(u8);
+
+    impl std::fmt::Debug for NoDebugOnPass {
+        fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            panic!("failure message built for a passing assertion")
+        }
+    }
+
+    #[test]
+    fn assert_should_not_build_messages_when_passing() {
+        let mut assert = assert::new();
+        assert.that(NoDebugOnPass(1)).is_eq(NoDebugOnPass(1)).not().is_eq(NoDebugOnPass(2));
+        assert.that(vec![NoDebugOnPass(1)]).has_len(1).contains(NoDebugOnPass(1)).eq_each(&[NoDebugOnPass(1)]);
+        assert
+            .that(NoDebugOnPass(1))
+            .is_eq_by(NoDebugOnPass(1), fn_comparator!(|a: &NoDebugOnPass, e: &NoDebugOnPass| a.0 == e.0));
+    }
+
     #[derive(Debug, PartialEq)]
     struct BacktraceSum {
         f: Vec<FrameSum>,
@@ -204,7 +468,7 @@ expectation: `"a"`"#
     impl From<&backtrace::Backtrace> for BacktraceSum {
         fn from(bt: &backtrace::Backtrace) -> Self {
             BacktraceSum {
-                f: bt.frames().iter().map(|f| FrameSum::from(f)).collect(),
+                f: bt.frames().iter().map(FrameSum::from).collect(),
             }
         }
     }
@@ -215,7 +479,7 @@ expectation: `"a"`"#
                 v: f.symbols()
                     .iter()
                     .map(|s| FrameSymSum {
-                        name: s.name().map(|n| n.as_str().unwrap_or_default().to_string()).unwrap_or(String::new()),
+                        name: s.name().map(|n| format!("{:#}", n)).unwrap_or_default(),
                         line: s.lineno().unwrap_or(0),
                         file: s.filename().map(|f| f.to_path_buf()).unwrap_or(PathBuf::new()),
                     })
@@ -0,0 +1,382 @@
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugValue {
+    Scalar(String),
+    Struct { name: String, fields: Vec<(String, DebugValue)> },
+    Tuple { name: String, items: Vec<DebugValue> },
+    Seq(Vec<DebugValue>),
+    Map(Vec<(DebugValue, DebugValue)>),
+    Set(Vec<DebugValue>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+    Key(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugMismatch {
+    pub path: Vec<PathSegment>,
+    pub actual: Option<DebugValue>,
+    pub expected: Option<DebugValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Punct(char),
+    Str(String),
+    Atom(String),
+}
+
+impl DebugValue {
+    pub fn parse(repr: &str) -> Option<DebugValue> {
+        let tokens = tokenize(repr)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let value = parser.value()?;
+        if parser.pos != parser.tokens.len() {
+            return None;
+        }
+        Some(value)
+    }
+
+    pub fn diff(&self, other: &DebugValue) -> Vec<DebugMismatch> {
+        self.diff_ignoring(other, &[])
+    }
+
+    pub fn diff_ignoring(&self, other: &DebugValue, ignored: &[&str]) -> Vec<DebugMismatch> {
+        let patterns: Vec<Vec<String>> = ignored.iter().map(|p| parse_pattern(p)).collect();
+        let mut mismatches = vec![];
+        diff_at(Some(self), Some(other), &mut vec![], &patterns, &mut mismatches);
+        mismatches
+    }
+}
+
+fn diff_at(a: Option<&DebugValue>, e: Option<&DebugValue>, path: &mut Vec<PathSegment>, ignored: &[Vec<String>], out: &mut Vec<DebugMismatch>) {
+    if ignored.iter().any(|p| pattern_matches(p, path)) {
+        return;
+    }
+    let mut mismatch = || {
+        out.push(DebugMismatch {
+            path: path.clone(),
+            actual: a.cloned(),
+            expected: e.cloned(),
+        })
+    };
+    match (a, e) {
+        (Some(DebugValue::Struct { name, fields }), Some(DebugValue::Struct { name: e_name, fields: e_fields })) if name == e_name => {
+            for (f_name, f_value) in fields {
+                path.push(PathSegment::Field(f_name.clone()));
+                let e_value = e_fields.iter().find(|(n, _)| n == f_name).map(|(_, v)| v);
+                diff_at(Some(f_value), e_value, path, ignored, out);
+                path.pop();
+            }
+            for (f_name, f_value) in e_fields.iter().filter(|(n, _)| !fields.iter().any(|(a_n, _)| a_n == n)) {
+                path.push(PathSegment::Field(f_name.clone()));
+                diff_at(None, Some(f_value), path, ignored, out);
+                path.pop();
+            }
+        }
+        (Some(DebugValue::Tuple { name, items }), Some(DebugValue::Tuple { name: e_name, items: e_items })) if name == e_name && items.len() == e_items.len() => {
+            for (i, (item, e_item)) in items.iter().zip(e_items.iter()).enumerate() {
+                path.push(PathSegment::Field(i.to_string()));
+                diff_at(Some(item), Some(e_item), path, ignored, out);
+                path.pop();
+            }
+        }
+        (Some(DebugValue::Seq(items)), Some(DebugValue::Seq(e_items))) => {
+            for i in 0..items.len().max(e_items.len()) {
+                path.push(PathSegment::Index(i));
+                diff_at(items.get(i), e_items.get(i), path, ignored, out);
+                path.pop();
+            }
+        }
+        (Some(DebugValue::Map(entries)), Some(DebugValue::Map(e_entries))) => {
+            for (key, value) in entries {
+                path.push(PathSegment::Key(key.to_string()));
+                let e_value = e_entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+                diff_at(Some(value), e_value, path, ignored, out);
+                path.pop();
+            }
+            for (key, e_value) in e_entries.iter().filter(|(k, _)| !entries.iter().any(|(a_k, _)| a_k == k)) {
+                path.push(PathSegment::Key(key.to_string()));
+                diff_at(None, Some(e_value), path, ignored, out);
+                path.pop();
+            }
+        }
+        (Some(DebugValue::Set(items)), Some(DebugValue::Set(e_items))) => {
+            for item in items.iter().filter(|i| !e_items.contains(i)) {
+                path.push(PathSegment::Key(item.to_string()));
+                diff_at(Some(item), None, path, ignored, out);
+                path.pop();
+            }
+            for e_item in e_items.iter().filter(|i| !items.contains(i)) {
+                path.push(PathSegment::Key(e_item.to_string()));
+                diff_at(None, Some(e_item), path, ignored, out);
+                path.pop();
+            }
+        }
+        (a, e) if a != e => mismatch(),
+        _ => {}
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Vec<String> {
+    let mut segments = vec![];
+    let mut current = String::new();
+    for c in pattern.chars() {
+        match c {
+            '.' | '[' | ']' => {
+                if !current.is_empty() {
+                    segments.push(current.clone());
+                    current.clear();
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
+
+fn pattern_matches(pattern: &[String], path: &[PathSegment]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((p, p_rest)) if p == "**" => (0..=path.len()).any(|skip| pattern_matches(p_rest, &path[skip..])),
+        Some((p, p_rest)) => match path.split_first() {
+            None => false,
+            Some((s, s_rest)) => {
+                let segment_ok = match s {
+                    PathSegment::Field(name) => p == "*" || p == name,
+                    PathSegment::Index(i) => p == "*" || p == &i.to_string(),
+                    PathSegment::Key(key) => p == "*" || p == key || p.trim_matches('"') == key.trim_matches('"'),
+                };
+                segment_ok && pattern_matches(p_rest, s_rest)
+            }
+        },
+    }
+}
+
+fn tokenize(repr: &str) -> Option<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = repr.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '{' | '}' | '[' | ']' | '(' | ')' | ',' | ':' => {
+                chars.next();
+                tokens.push(Token::Punct(c));
+            }
+            '"' | '\'' => tokens.push(Token::Str(quoted(&mut chars)?)),
+            _ => {
+                let mut atom = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "{}[](),:\"".contains(c) {
+                        break;
+                    }
+                    atom.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Atom(atom));
+            }
+        }
+    }
+    Some(tokens)
+}
+
+fn quoted(chars: &mut Peekable<Chars>) -> Option<String> {
+    let quote = chars.next()?;
+    let mut literal = quote.to_string();
+    loop {
+        let c = chars.next()?;
+        literal.push(c);
+        if c == '\\' {
+            literal.push(chars.next()?);
+        } else if c == quote {
+            return Some(literal);
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn value(&mut self) -> Option<DebugValue> {
+        match self.next()? {
+            Token::Punct('[') => Some(DebugValue::Seq(self.items(']')?)),
+            Token::Punct('(') => Some(DebugValue::Tuple {
+                name: String::new(),
+                items: self.items(')')?,
+            }),
+            Token::Punct('{') => self.entries(),
+            Token::Str(s) => Some(DebugValue::Scalar(s)),
+            Token::Atom(name) => {
+                if self.eat('{') {
+                    Some(DebugValue::Struct { name, fields: self.fields()? })
+                } else if self.eat('(') {
+                    Some(DebugValue::Tuple { name, items: self.items(')')? })
+                } else {
+                    Some(DebugValue::Scalar(name))
+                }
+            }
+            Token::Punct(_) => None,
+        }
+    }
+
+    fn items(&mut self, close: char) -> Option<Vec<DebugValue>> {
+        let mut items = vec![];
+        while !self.eat(close) {
+            items.push(self.value()?);
+            if !self.eat(',') && self.peek() != Some(&Token::Punct(close)) {
+                return None;
+            }
+        }
+        Some(items)
+    }
+
+    fn entries(&mut self) -> Option<DebugValue> {
+        let mut entries = vec![];
+        let mut items = vec![];
+        while !self.eat('}') {
+            let key = self.value()?;
+            if self.eat(':') {
+                entries.push((key, self.value()?));
+            } else {
+                items.push(key);
+            }
+            if !self.eat(',') && self.peek() != Some(&Token::Punct('}')) {
+                return None;
+            }
+        }
+        match (entries.is_empty(), items.is_empty()) {
+            (_, true) => Some(DebugValue::Map(entries)),
+            (true, false) => Some(DebugValue::Set(items)),
+            (false, false) => None,
+        }
+    }
+
+    fn fields(&mut self) -> Option<Vec<(String, DebugValue)>> {
+        let mut fields = vec![];
+        while !self.eat('}') {
+            let name = match self.next()? {
+                Token::Atom(name) if name == ".." => continue,
+                Token::Atom(name) => name,
+                _ => return None,
+            };
+            if !self.eat(':') {
+                return None;
+            }
+            fields.push((name, self.value()?));
+            if !self.eat(',') && self.peek() != Some(&Token::Punct('}')) {
+                return None;
+            }
+        }
+        Some(fields)
+    }
+}
+
+fn write_items<'a, I: Iterator<Item = &'a DebugValue>>(f: &mut Formatter<'_>, items: I) -> std::fmt::Result {
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+impl Display for DebugValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DebugValue::Scalar(s) => f.write_str(s),
+            DebugValue::Struct { name, fields } => {
+                write!(f, "{} {{ ", name)?;
+                for (i, (f_name, f_value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", f_name, f_value)?;
+                }
+                f.write_str(" }")
+            }
+            DebugValue::Tuple { name, items } => {
+                write!(f, "{}(", name)?;
+                write_items(f, items.iter())?;
+                f.write_str(")")
+            }
+            DebugValue::Seq(items) => {
+                f.write_str("[")?;
+                write_items(f, items.iter())?;
+                f.write_str("]")
+            }
+            DebugValue::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                f.write_str("}")
+            }
+            DebugValue::Set(items) => {
+                f.write_str("{")?;
+                write_items(f, items.iter())?;
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, ".{}", name),
+            PathSegment::Index(i) => write!(f, "[{}]", i),
+            PathSegment::Key(key) => write!(f, "[{}]", key),
+        }
+    }
+}
+
+impl Display for DebugMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("at ")?;
+        if self.path.is_empty() {
+            f.write_str(".")?;
+        }
+        for s in self.path.iter() {
+            write!(f, "{}", s)?;
+        }
+        let side = |v: &Option<DebugValue>| v.as_ref().map(|v| v.to_string()).unwrap_or_else(|| "<missing>".to_string());
+        write!(f, ": {} != {}", side(&self.actual), side(&self.expected))
+    }
+}
@@ -0,0 +1,270 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert::{DebugMismatch, DebugValue, PathSegment};
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+    use std::fmt::{Debug, Formatter};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Meta {
+        created_at: u64,
+        tags: Vec<&'static str>,
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Item {
+        id: u32,
+        label: String,
+        price: (u32, char),
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Order {
+        id: u32,
+        meta: Meta,
+        items: Vec<Item>,
+        note: Option<String>,
+    }
+
+    fn order(id: u32, created_at: u64, price: u32) -> Order {
+        Order {
+            id,
+            meta: Meta {
+                created_at,
+                tags: vec!["a, b", "\"quoted\""],
+            },
+            items: vec![
+                Item {
+                    id: id * 10,
+                    label: String::from("first"),
+                    price: (10, ','),
+                },
+                Item {
+                    id: id * 10 + 1,
+                    label: String::from("second"),
+                    price: (price, '€'),
+                },
+            ],
+            note: None,
+        }
+    }
+
+    fn parse<T: std::fmt::Debug>(v: &T) -> DebugValue {
+        DebugValue::parse(&format!("{:#?}", v)).unwrap()
+    }
+
+    #[test]
+    fn parse_should_build_value_tree() {
+        assert_eq!(
+            parse(&(1, "a", vec![Some(2.5)])),
+            DebugValue::Tuple {
+                name: String::new(),
+                items: vec![
+                    DebugValue::Scalar("1".to_string()),
+                    DebugValue::Scalar("\"a\"".to_string()),
+                    DebugValue::Seq(vec![DebugValue::Tuple {
+                        name: "Some".to_string(),
+                        items: vec![DebugValue::Scalar("2.5".to_string())]
+                    }]),
+                ]
+            }
+        );
+        assert_eq!(
+            parse(&Meta { created_at: 3, tags: vec![] }),
+            DebugValue::Struct {
+                name: "Meta".to_string(),
+                fields: vec![("created_at".to_string(), DebugValue::Scalar("3".to_string())), ("tags".to_string(), DebugValue::Seq(vec![]))]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_accept_compact_and_pretty_representations() {
+        let o = order(1, 2, 3);
+        assert_eq!(DebugValue::parse(&format!("{:?}", o)), DebugValue::parse(&format!("{:#?}", o)));
+        assert_eq!(parse(&o).to_string(), format!("{:?}", o));
+    }
+
+    #[test]
+    fn parse_should_reject_malformed_representations() {
+        assert_eq!(DebugValue::parse("S { a: 1"), None);
+        assert_eq!(DebugValue::parse("[1, 2"), None);
+        assert_eq!(DebugValue::parse("\"open"), None);
+        assert_eq!(DebugValue::parse("S { a 1 }"), None);
+        assert_eq!(DebugValue::parse("1 2"), None);
+    }
+
+    #[test]
+    fn diff_ignoring_should_list_mismatches_by_path() {
+        let mismatches = parse(&order(1, 2, 3)).diff_ignoring(&parse(&order(1, 2, 4)), &[]);
+        assert_eq!(
+            mismatches,
+            vec![DebugMismatch {
+                path: vec![
+                    PathSegment::Field("items".to_string()),
+                    PathSegment::Index(1),
+                    PathSegment::Field("price".to_string()),
+                    PathSegment::Field("0".to_string())
+                ],
+                actual: Some(DebugValue::Scalar("3".to_string())),
+                expected: Some(DebugValue::Scalar("4".to_string())),
+            }]
+        );
+        assert_eq!(mismatches[0].to_string(), "at .items[1].price.0: 3 != 4");
+    }
+
+    #[test]
+    fn diff_ignoring_should_skip_ignored_paths() {
+        let a = parse(&order(1, 2, 3));
+        let b = parse(&order(2, 5, 3));
+        let render = |ignored: &[&str]| a.diff_ignoring(&b, ignored).iter().map(|m| m.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            render(&[]),
+            vec!["at .id: 1 != 2", "at .meta.created_at: 2 != 5", "at .items[0].id: 10 != 20", "at .items[1].id: 11 != 21"]
+        );
+        assert_eq!(render(&["id", "meta.created_at"]), vec!["at .items[0].id: 10 != 20", "at .items[1].id: 11 != 21"]);
+        assert_eq!(render(&["id", "meta.*", "items[*].id"]), Vec::<String>::new());
+        assert_eq!(render(&["**.id", "meta"]), Vec::<String>::new());
+        assert_eq!(render(&["items.1.id", ".id", "meta.created_at"]), vec!["at .items[0].id: 10 != 20"]);
+    }
+
+    #[test]
+    fn diff_ignoring_should_report_missing_elements() {
+        let mismatches = parse(&vec![1, 2]).diff_ignoring(&parse(&vec![1, 2, 3]), &[]);
+        assert_eq!(mismatches.iter().map(|m| m.to_string()).collect::<Vec<_>>(), vec!["at [2]: <missing> != 3"]);
+
+        let mismatches = parse(&Some(1)).diff_ignoring(&parse(&None::<i32>), &[]);
+        assert_eq!(mismatches.iter().map(|m| m.to_string()).collect::<Vec<_>>(), vec!["at .: Some(1) != None"]);
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Event {
+        Started,
+        Moved(i32, i32),
+        Renamed { from: String, to: String },
+    }
+
+    struct Opaque {
+        visible: u8,
+    }
+
+    impl Debug for Opaque {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Opaque").field("visible", &self.visible).finish_non_exhaustive()
+        }
+    }
+
+    fn render(mismatches: Vec<DebugMismatch>) -> Vec<String> {
+        mismatches.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_should_handle_maps_and_sets() {
+        let mut scores = BTreeMap::new();
+        scores.insert("alice", vec![1, 2]);
+        scores.insert("bob", vec![]);
+        assert_eq!(
+            parse(&scores),
+            DebugValue::Map(vec![
+                (
+                    DebugValue::Scalar("\"alice\"".to_string()),
+                    DebugValue::Seq(vec![DebugValue::Scalar("1".to_string()), DebugValue::Scalar("2".to_string())])
+                ),
+                (DebugValue::Scalar("\"bob\"".to_string()), DebugValue::Seq(vec![])),
+            ])
+        );
+        assert_eq!(parse(&BTreeMap::<u8, u8>::new()), DebugValue::Map(vec![]));
+        assert_eq!(
+            parse(&[1, 2].iter().collect::<BTreeSet<_>>()),
+            DebugValue::Set(vec![DebugValue::Scalar("1".to_string()), DebugValue::Scalar("2".to_string())])
+        );
+        assert_eq!(DebugValue::parse("{1: 2, 3}"), None);
+    }
+
+    #[test]
+    fn parse_should_handle_enums_escapes_and_non_exhaustive_structs() {
+        assert_eq!(parse(&Event::Started), DebugValue::Scalar("Started".to_string()));
+        assert_eq!(parse(&Event::Moved(-1, 2)).to_string(), "Moved(-1, 2)");
+        assert_eq!(
+            parse(&Event::Renamed {
+                from: String::from("a\"}, {b"),
+                to: String::from("line\nbreak\\"),
+            })
+            .to_string(),
+            r#"Renamed { from: "a\"}, {b", to: "line\nbreak\\" }"#
+        );
+        assert_eq!(parse(&vec!['\'', ',', '\n']).to_string(), r#"['\'', ',', '\n']"#);
+        assert_eq!(
+            parse(&Opaque { visible: 1 }),
+            DebugValue::Struct {
+                name: "Opaque".to_string(),
+                fields: vec![("visible".to_string(), DebugValue::Scalar("1".to_string()))]
+            }
+        );
+    }
+
+    #[test]
+    fn diff_should_compare_maps_by_key() {
+        let mut a = HashMap::new();
+        let mut b = HashMap::new();
+        for i in 0..20 {
+            a.insert(format!("k{}", i), i);
+            b.insert(format!("k{}", i), if i == 7 { 70 } else { i });
+        }
+        a.insert("only_actual".to_string(), 1);
+        b.insert("only_expected".to_string(), 2);
+
+        let mut mismatches = render(parse(&a).diff(&parse(&b)));
+        mismatches.sort();
+        assert_eq!(
+            mismatches,
+            vec![r#"at ["k7"]: 7 != 70"#, r#"at ["only_actual"]: 1 != <missing>"#, r#"at ["only_expected"]: <missing> != 2"#]
+        );
+    }
+
+    #[test]
+    fn diff_should_compare_sets_by_element() {
+        let a: BTreeSet<_> = [1, 2, 3].iter().cloned().collect();
+        let b: BTreeSet<_> = [2, 3, 4].iter().cloned().collect();
+        assert_eq!(render(parse(&a).diff(&parse(&b))), vec!["at [1]: 1 != <missing>", "at [4]: <missing> != 4"]);
+    }
+
+    #[test]
+    fn diff_should_report_changed_variants_at_their_path() {
+        let a = vec![
+            Event::Started,
+            Event::Moved(1, 2),
+            Event::Renamed {
+                from: "a".to_string(),
+                to: "b".to_string(),
+            },
+        ];
+        let b = vec![
+            Event::Moved(0, 0),
+            Event::Moved(1, 3),
+            Event::Renamed {
+                from: "a".to_string(),
+                to: "c".to_string(),
+            },
+        ];
+        assert_eq!(
+            render(parse(&a).diff(&parse(&b))),
+            vec!["at [0]: Started != Moved(0, 0)", "at [1].1: 2 != 3", r#"at [2].to: "b" != "c""#]
+        );
+    }
+
+    #[test]
+    fn diff_ignoring_should_match_map_keys() {
+        let mut a = BTreeMap::new();
+        a.insert("alice", Meta { created_at: 1, tags: vec![] });
+        let mut b = BTreeMap::new();
+        b.insert("alice", Meta { created_at: 2, tags: vec![] });
+        assert_eq!(render(parse(&a).diff_ignoring(&parse(&b), &["alice.created_at"])), Vec::<String>::new());
+        assert_eq!(render(parse(&a).diff_ignoring(&parse(&b), &["[\"alice\"].created_at"])), Vec::<String>::new());
+        assert_eq!(render(parse(&a).diff_ignoring(&parse(&b), &["bob"])), vec![r#"at ["alice"].created_at: 1 != 2"#]);
+    }
+}
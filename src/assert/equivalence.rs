@@ -0,0 +1,154 @@
+use crate::assert::{Assertion, Execution, Instance};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+type ComparatorFn<A, E> = dyn Fn(&A, &E) -> bool;
+
+pub struct Comparator<A: ?Sized, E: ?Sized> {
+    c_fn: Box<ComparatorFn<A, E>>,
+    c_name: String,
+}
+
+impl<A: ?Sized, E: ?Sized> Comparator<A, E> {
+    pub fn new<F>(c_name: &str, c_fn: F) -> Self
+    where
+        F: Fn(&A, &E) -> bool + 'static,
+    {
+        Comparator {
+            c_fn: Box::new(c_fn),
+            c_name: c_name.to_string(),
+        }
+    }
+
+    pub fn compare(&self, a: &A, e: &E) -> bool {
+        (self.c_fn)(a, e)
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.c_name
+    }
+}
+
+#[macro_export]
+macro_rules! fn_comparator {
+    ($result:expr) => {
+        $crate::assert::Comparator::new(stringify!($result), $result)
+    };
+}
+
+#[derive(Default)]
+pub struct Equivalences {
+    comparators: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Equivalences {
+    fn insert<T: ?Sized + 'static>(&mut self, comparator: Comparator<T, T>) {
+        self.comparators.insert(TypeId::of::<T>(), Box::new(Rc::new(comparator)));
+    }
+
+    fn get<T: ?Sized + 'static>(&self) -> Option<Rc<Comparator<T, T>>> {
+        self.comparators.get(&TypeId::of::<T>())?.downcast_ref::<Rc<Comparator<T, T>>>().cloned()
+    }
+}
+
+pub struct ByPartialEq;
+pub struct ByDeref;
+pub struct ByPath;
+
+pub trait EqExpectation<A: ?Sized, M> {
+    fn eq_actual(&self, actual: &A) -> bool;
+
+    // Registered equivalences are looked up by the `TypeId` of the actual type, which only exists for `'static`
+    // types. The blanket `PartialEq` impl cannot require `'static` without rejecting borrowed actuals such as
+    // `&str` slices of locals, so only expectations passed by reference (`is_eq(&0.3)`) use the registry while
+    // by-value ones (`is_eq(0.3)`) fall back to `eq_actual`.
+    fn equivalence(&self, _equivalences: &Equivalences) -> Option<(Rc<Comparator<A, A>>, &A)> {
+        None
+    }
+}
+
+impl<A, E> EqExpectation<A, ByPartialEq> for E
+where
+    A: PartialEq<E> + ?Sized,
+{
+    fn eq_actual(&self, actual: &A) -> bool {
+        actual == self
+    }
+}
+
+impl<A, E> EqExpectation<A, ByDeref> for E
+where
+    A: PartialEq + ?Sized + 'static,
+    E: Deref<Target = A>,
+{
+    fn eq_actual(&self, actual: &A) -> bool {
+        actual == self.deref()
+    }
+
+    fn equivalence(&self, equivalences: &Equivalences) -> Option<(Rc<Comparator<A, A>>, &A)> {
+        Some((equivalences.get::<A>()?, self.deref()))
+    }
+}
+
+impl EqExpectation<PathBuf, ByPath> for &str {
+    fn eq_actual(&self, actual: &PathBuf) -> bool {
+        actual.as_path() == Path::new(self)
+    }
+}
+
+pub(super) fn expectation_eq<A, E, M>(equivalences: &Equivalences, actual: &A, expected: &E) -> bool
+where
+    A: ?Sized,
+    E: EqExpectation<A, M>,
+{
+    match expected.equivalence(equivalences) {
+        Some((c, e)) => c.compare(actual, e),
+        None => expected.eq_actual(actual),
+    }
+}
+
+impl Assertion {
+    pub fn register_eq<T: ?Sized + 'static>(&mut self, comparator: Comparator<T, T>) -> &mut Self {
+        self.rca.borrow_mut().equivalences.insert(comparator);
+        self
+    }
+}
+
+impl<A> Instance<'_, A>
+where
+    A: Debug + ?Sized,
+{
+    pub fn is_eq_by<E>(&mut self, expected: E, comparator: Comparator<A, E>) -> &mut Self
+    where
+        E: Debug,
+    {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let ok = comparator.compare(self.actual.as_ref(), &expected);
+        self.handle_execution(Execution {
+            ok,
+            log: || {
+                format!(
+                    r#"assertion failed: `(actual == expectation)` by `{}`
+     actual: `{:?}`
+expectation: `{:?}`"#,
+                    comparator.c_name, self.actual, expected
+                )
+            },
+            nlog: || {
+                format!(
+                    r#"assertion failed: `(actual != expectation)` by `{}`
+     actual: `{:?}`
+expectation: `{:?}`"#,
+                    comparator.c_name, self.actual, expected
+                )
+            },
+        });
+        self.finish()
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::assert::{Comparator, FailResult};
+    use crate::assert_panic_ignored;
+    use crate::fn_comparator;
+    use std::panic;
+    use std::path::PathBuf;
+
+    #[test]
+    fn assert_eq_by_should_pass() {
+        let mut assert = assert::new();
+        assert.that(1.0).is_eq_by(1.05, fn_comparator!(|a: &f64, e: &f64| (a - e).abs() < 0.1));
+        assert.that(1.0).not().is_eq_by(1.5, fn_comparator!(|a: &f64, e: &f64| (a - e).abs() < 0.1));
+        assert
+            .that(String::from("Hello"))
+            .is_eq_by("hello", Comparator::new("case insensitive", |a: &String, e: &&str| a.eq_ignore_ascii_case(e)));
+        assert
+            .that(PathBuf::from("a/./b"))
+            .is_eq_by(PathBuf::from("a/b"), Comparator::new("normalized", |a: &PathBuf, e: &PathBuf| a.components().eq(e.components())));
+    }
+
+    #[test]
+    fn assert_eq_by_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)` by `|a: &f64, e: &f64| (a - e).abs() < 0.1`
+     actual: `1.0`
+expectation: `1.5`"#
+                    );
+                })
+            });
+            assert.that(1.0).is_eq_by(1.5, fn_comparator!(|a: &f64, e: &f64| (a - e).abs() < 0.1));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_eq_by_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual != expectation)` by `case insensitive`
+     actual: `"A"`
+expectation: `"a"`"#
+                    );
+                })
+            });
+            assert
+                .that("A")
+                .not()
+                .is_eq_by("a", Comparator::new("case insensitive", |a: &&str, e: &&str| a.eq_ignore_ascii_case(e)));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn registered_equivalence_should_be_used_by_is_eq_contains_and_eq_each() {
+        let mut assert = assert::new();
+        assert.register_eq(Comparator::new("within 1e-9", |a: &f64, e: &f64| (a - e).abs() < 1e-9));
+
+        assert.that(0.1 + 0.2).is_eq(&0.3);
+        assert.that(0.1 + 0.2).not().is_eq(&0.4);
+        assert.that(vec![0.1 + 0.2, 1.0]).contains(&0.3);
+        assert.that(vec![0.1 + 0.2, 1.0]).not().contains(&0.4);
+        assert.that(vec![0.1 + 0.2, 0.7 + 0.1]).eq_each(&[&0.3, &0.8]);
+
+        // By-value expectations compare with `PartialEq`, see `EqExpectation::equivalence`.
+        assert.that(vec![0.1 + 0.2]).not().is_eq(vec![0.3]);
+        assert.that(0.1 + 0.2).not().is_eq(0.3);
+        assert.that(vec![0.1 + 0.2]).not().contains(0.3);
+        assert.that(1).is_eq(1);
+    }
+
+    #[test]
+    fn registered_equivalence_should_apply_to_borrowed_values() {
+        let mut assert = assert::new();
+        assert.register_eq(Comparator::new("case insensitive", |a: &str, e: &str| a.eq_ignore_ascii_case(e)));
+
+        let actual = String::from("HELLO");
+        assert.that(String::from("hello")).transform(|a| Box::<str>::from(a.as_str())).is_eq(actual.as_str());
+    }
+
+    #[test]
+    fn registered_equivalence_should_only_apply_to_static_actuals() {
+        let mut assert = assert::new();
+        assert.register_eq(Comparator::new("always", |_: &&'static str, _: &&'static str| true));
+        assert.that("a").is_eq(&"b");
+
+        let owned = String::from("a");
+        assert.that(owned.as_str()).not().is_eq("b");
+        assert.that(vec![owned.as_str()]).not().contains("b");
+    }
+
+    #[test]
+    fn registered_equivalence_should_be_scoped_to_its_assertion() {
+        let mut assert = assert::new();
+        assert.register_eq(Comparator::new("always", |_: &i32, _: &i32| true));
+        assert.that(1).is_eq(&2);
+
+        let mut other = assert::new();
+        other.that(1).not().is_eq(&2);
+    }
+
+    #[test]
+    fn registered_equivalence_should_fail_with_its_name() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)` by `within 1e-9`
+     actual: `0.30000000000000004`
+expectation: `0.31`"#
+                    );
+                })
+            });
+            assert.register_eq(Comparator::new("within 1e-9", |a: &f64, e: &f64| (a - e).abs() < 1e-9));
+            assert.that(0.1 + 0.2).is_eq(&0.31);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn registered_equivalence_should_fail_eq_each_by_position() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(expectation[1] = actual[1])`");
+                })
+            });
+            assert.register_eq(Comparator::new("within 1e-9", |a: &f64, e: &f64| (a - e).abs() < 1e-9));
+            assert.that(vec![0.1 + 0.2, 0.5]).eq_each(&[&0.3, &0.6]);
+        });
+        assert_panic_ignored!(result)
+    }
+}
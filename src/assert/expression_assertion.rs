@@ -0,0 +1,98 @@
+#[macro_export]
+macro_rules! assert_that {
+    ($assertion:expr, $actual:expr) => {
+        $assertion.that_expression($actual, stringify!($actual))
+    };
+}
+
+#[macro_export]
+macro_rules! check {
+    ($assertion:expr, $($condition:tt)+) => {
+        $crate::__check_scan!(($assertion) [$($condition)+] [] [] [] [] $($condition)+)
+    };
+}
+
+// Splits the condition on its top level comparison operator.
+// State: (assertion) [whole condition] [(left operand) operator] [current operand] [generics depth] [after `::`]
+// Every token is classified through a copy of itself so that the original token, and its spacing, is kept.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_scan {
+    // turbofish generics: comparison tokens inside them are part of the operand
+    (@ (<) $t:tt ($a:expr) $all:tt $done:tt [$($cur:tt)*] [$($depth:tt)*] [::] $($rest:tt)*) => {
+        $crate::__check_scan!(($a) $all $done [$($cur)* $t] [$t $($depth)*] [] $($rest)*)
+    };
+    (@ (<) $t:tt ($a:expr) $all:tt $done:tt [$($cur:tt)*] [$d:tt $($depth:tt)*] [] $($rest:tt)*) => {
+        $crate::__check_scan!(($a) $all $done [$($cur)* $t] [$t $d $($depth)*] [] $($rest)*)
+    };
+    (@ (>) $t:tt ($a:expr) $all:tt $done:tt [$($cur:tt)*] [$d:tt $($depth:tt)*] [] $($rest:tt)*) => {
+        $crate::__check_scan!(($a) $all $done [$($cur)* $t] [$($depth)*] [] $($rest)*)
+    };
+    (@ (>>) $t:tt ($a:expr) $all:tt $done:tt [$($cur:tt)*] [$d1:tt $d2:tt $($depth:tt)*] [] $($rest:tt)*) => {
+        $crate::__check_scan!(($a) $all $done [$($cur)* $t] [$($depth)*] [] $($rest)*)
+    };
+    (@ (::) $t:tt ($a:expr) $all:tt $done:tt [$($cur:tt)*] $depth:tt $c:tt $($rest:tt)*) => {
+        $crate::__check_scan!(($a) $all $done [$($cur)* $t] $depth [::] $($rest)*)
+    };
+    (@ $k:tt $t:tt ($a:expr) $all:tt $done:tt [$($cur:tt)*] [$d:tt $($depth:tt)*] $c:tt $($rest:tt)*) => {
+        $crate::__check_scan!(($a) $all $done [$($cur)* $t] [$d $($depth)*] [] $($rest)*)
+    };
+    // logical operators: the condition is checked as a whole
+    (@ (&&) $t:tt ($a:expr) [$($all:tt)+] $done:tt $cur:tt [] $c:tt $($rest:tt)*) => {
+        $crate::__check_bool!(($a) $($all)+)
+    };
+    (@ (||) $t:tt ($a:expr) [$($all:tt)+] $done:tt $cur:tt [] $c:tt $($rest:tt)*) => {
+        $crate::__check_bool!(($a) $($all)+)
+    };
+    // comparison operators
+    (@ (==) $t:tt ($a:expr) $all:tt [] [$($cur:tt)+] [] $c:tt $($rest:tt)+) => {
+        $crate::__check_scan!(($a) $all [($($cur)+) $t] [] [] [] $($rest)+)
+    };
+    (@ (!=) $t:tt ($a:expr) $all:tt [] [$($cur:tt)+] [] $c:tt $($rest:tt)+) => {
+        $crate::__check_scan!(($a) $all [($($cur)+) $t] [] [] [] $($rest)+)
+    };
+    (@ (<=) $t:tt ($a:expr) $all:tt [] [$($cur:tt)+] [] $c:tt $($rest:tt)+) => {
+        $crate::__check_scan!(($a) $all [($($cur)+) $t] [] [] [] $($rest)+)
+    };
+    (@ (>=) $t:tt ($a:expr) $all:tt [] [$($cur:tt)+] [] $c:tt $($rest:tt)+) => {
+        $crate::__check_scan!(($a) $all [($($cur)+) $t] [] [] [] $($rest)+)
+    };
+    (@ (<) $t:tt ($a:expr) $all:tt [] [$($cur:tt)+] [] $c:tt $($rest:tt)+) => {
+        $crate::__check_scan!(($a) $all [($($cur)+) $t] [] [] [] $($rest)+)
+    };
+    (@ (>) $t:tt ($a:expr) $all:tt [] [$($cur:tt)+] [] $c:tt $($rest:tt)+) => {
+        $crate::__check_scan!(($a) $all [($($cur)+) $t] [] [] [] $($rest)+)
+    };
+    (@ $k:tt $t:tt ($a:expr) $all:tt $done:tt [$($cur:tt)*] [] $c:tt $($rest:tt)*) => {
+        $crate::__check_scan!(($a) $all $done [$($cur)* $t] [] [] $($rest)*)
+    };
+    // end of the condition
+    (($a:expr) [$($all:tt)+] [($($l:tt)+) $op:tt] [$($r:tt)+] [] $c:tt) => {
+        match (&($($l)+), &($($r)+)) {
+            (left, right) => $a.check(*left $op *right, || {
+                format!(
+                    "assertion failed: `({})`\n  `{}` → `{:?}`\n  `{}` → `{:?}`",
+                    stringify!($($all)+),
+                    stringify!($($l)+),
+                    left,
+                    stringify!($($r)+),
+                    right
+                )
+            }),
+        }
+    };
+    (($a:expr) [$($all:tt)+] $done:tt $cur:tt $depth:tt $c:tt) => {
+        $crate::__check_bool!(($a) $($all)+)
+    };
+    (($a:expr) $all:tt $done:tt $cur:tt $depth:tt $c:tt $next:tt $($rest:tt)*) => {
+        $crate::__check_scan!(@ ($next) $next ($a) $all $done $cur $depth $c $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_bool {
+    (($a:expr) $($all:tt)+) => {
+        $a.check($($all)+, || format!("assertion failed: `({})`", stringify!($($all)+)))
+    };
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::assert::FailResult;
+    use crate::assert_panic_ignored;
+    use crate::{assert_that, check};
+    use std::panic;
+
+    #[test]
+    fn check_should_pass() {
+        let mut assert = assert::new();
+        let x = [1, 2, 3_i32];
+        let expected = 3_usize;
+        check!(assert, x.len() == expected);
+        check!(assert, x.len() != 4);
+        check!(assert, x[0] < x[1]);
+        check!(assert, x[2] >= 3);
+        check!(assert, x.contains(&2));
+        check!(assert, !x.is_empty() && x[0] == 1);
+        check!(assert, x.iter().map(|i| i * 2).sum::<i32>() == 12);
+        check!(assert, x.iter().map(|i| i + 1).collect::<Vec<i32>>() == vec![2, 3, 4]);
+        check!(assert, x.len() > 2);
+        check!(assert, 1 << 2 == 4);
+    }
+
+    #[test]
+    fn check_should_fail_with_operands() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(x.len() == expected)`
+  `x.len()` → `3`
+  `expected` → `4`"#
+                    );
+                })
+            });
+            let x = [1, 2, 3];
+            let expected = 4;
+            check!(assert, x.len() == expected);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn check_should_fail_with_ordering_operands() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(name.to_uppercase().as_str() <= "ABC")`
+  `name.to_uppercase().as_str()` → `"XYZ"`
+  `"ABC"` → `"ABC"`"#
+                    );
+                })
+            });
+            let name = "xyz";
+            check!(assert, name.to_uppercase().as_str() <= "ABC");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn check_should_fail_with_turbofish_operands() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(x.iter().map(|i| i + 1).collect::<Vec<i32>>() > vec![4])`
+  `x.iter().map(|i| i + 1).collect::<Vec<i32>>()` → `[2, 3]`
+  `vec![4]` → `[4]`"#
+                    );
+                })
+            });
+            let x = [1, 2];
+            check!(assert, x.iter().map(|i| i + 1).collect::<Vec<i32>>() > vec![4]);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn check_should_fail_without_operands_on_logical_expressions() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(x.is_empty() || x[0] == 2)`");
+                })
+            });
+            let x = [1];
+            check!(assert, x.is_empty() || x[0] == 2);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn check_should_record_every_failure() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert!(fr.log.starts_with("assertion failed: `(x"));
+                })
+            });
+            let x = 1;
+            check!(assert, x == 2);
+            check!(assert, x > 2);
+            check!(assert, x < 1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_that_should_pass() {
+        let mut assert = assert::new();
+        let x = [1, 2, 3];
+        assert_that!(assert, x.len()).is_eq(3);
+        assert_that!(assert, x.to_vec()).contains(2);
+    }
+
+    #[test]
+    fn assert_that_should_fail_with_expression() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+     actual: `3`
+expectation: `4`
+ expression: `x.len()`"#
+                    );
+                })
+            });
+            let x = [1, 2, 3];
+            assert_that!(assert, x.len()).is_eq(4);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_that_should_fail_with_expression_and_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(expectation ∉ actual)`\n expression: `x.iter().map(|i| i * 2).collect::<Vec<_>>()`");
+                })
+            });
+            let x = [1, 2, 3];
+            assert_that!(assert, x.iter().map(|i| i * 2).collect::<Vec<_>>()).not().contains(4);
+        });
+        assert_panic_ignored!(result)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::assert::assertion::Actual;
+use crate::assert::{Execution, Instance};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+impl<K, V> Instance<'_, BTreeMap<K, V>>
+where
+    K: Debug + Ord,
+    V: Debug,
+{
+    pub fn contains_key(&mut self, key: K) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let ok = self.actual.contains_key(&key);
+        self.handle_execution(Execution {
+            ok,
+            log: || format!("assertion failed: `(key ∈ actual)`\n     actual: `{:?}`\n        key: `{:?}`", self.actual, key),
+            nlog: || format!("assertion failed: `(key ∉ actual)`\n     actual: `{:?}`\n        key: `{:?}`", self.actual, key),
+        });
+        self.finish()
+    }
+
+    pub fn value(&self, key: K) -> Instance<'_, V> {
+        if self.actual.is_missing() {
+            return self.projected("value", Actual::Missing);
+        }
+        if self.instance_config.negation {
+            self.handle_execution(Execution {
+                ok: true,
+                log: String::new,
+                nlog: || "value assertion cannot be negated".to_string(),
+            });
+            return self.projected("value", Actual::Missing);
+        }
+        let value = self.actual.get(&key);
+        self.handle_execution(Execution {
+            ok: value.is_some(),
+            log: || format!("assertion failed: `(actual[key] exists)`\n     actual: `{:?}`\n        key: `{:?}`", self.actual, key),
+            nlog: String::new,
+        });
+        match value {
+            Some(v) => self.projected("value", Actual::Borrowed(v)),
+            None => self.projected("value", Actual::Missing),
+        }
+    }
+}
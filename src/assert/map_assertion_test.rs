@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::assert::FailResult;
+    use crate::assert_panic_ignored;
+    use std::collections::BTreeMap;
+    use std::panic;
+
+    fn scores() -> BTreeMap<&'static str, u32> {
+        vec![("a", 1), ("b", 2)].into_iter().collect()
+    }
+
+    #[test]
+    fn assert_contains_key_should_pass() {
+        let mut assert = assert::new();
+        assert.that(scores()).contains_key("a").not().contains_key("c");
+        assert.that(scores()).value("b").is_eq(2);
+    }
+
+    #[test]
+    fn assert_contains_key_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(key ∈ actual)`
+     actual: `{"a": 1, "b": 2}`
+        key: `"c"`"#
+                    );
+                })
+            });
+            assert.that(scores()).contains_key("c");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_contains_key_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(key ∉ actual)`
+     actual: `{"a": 1, "b": 2}`
+        key: `"a"`"#
+                    );
+                })
+            });
+            assert.that(scores()).not().contains_key("a");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_value_should_fail_when_missing() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual[key] exists)`
+     actual: `{"a": 1, "b": 2}`
+        key: `"c"`"#
+                    );
+                })
+            });
+            assert.that(scores()).value("c").is_eq(3).not().is_eq(3);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_value_should_fail_with_projection_chain() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+     actual: `1`
+expectation: `3`
+ projection: `actual.value(..)`"#
+                    );
+                })
+            });
+            assert.that(scores()).value("a").is_eq(3);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_value_should_prevent_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "value assertion cannot be negated");
+                })
+            });
+            assert.that(scores()).not().value("a").is_eq(3);
+        });
+        assert_panic_ignored!(result)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::assert::{AsF64, Execution, Instance};
+use std::fmt::Debug;
+
+impl<T> Instance<'_, Vec<Vec<T>>>
+where
+    T: Debug,
+{
+    pub fn has_dimensions(&mut self, rows: usize, columns: usize) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let row_lens = self.actual.iter().map(Vec::len).collect::<Vec<_>>();
+        let ok = row_lens.len() == rows && row_lens.iter().all(|l| *l == columns);
+        let dimensions = || match row_lens.first() {
+            Some(l) if row_lens.iter().any(|r| r != l) => format!("{} rows of {:?} columns", row_lens.len(), row_lens),
+            Some(l) => format!("{}x{}", row_lens.len(), l),
+            None => "0x0".to_string(),
+        };
+        self.handle_execution(Execution {
+            ok,
+            log: || {
+                format!(
+                    "assertion failed: `(actual dimensions == expectation)`\n     actual: `{}`\nexpectation: `{}x{}`",
+                    dimensions(),
+                    rows,
+                    columns
+                )
+            },
+            nlog: || {
+                format!(
+                    "assertion failed: `(actual dimensions != expectation)`\n     actual: `{}`\nexpectation: `{}x{}`",
+                    dimensions(),
+                    rows,
+                    columns
+                )
+            },
+        });
+        self.finish()
+    }
+
+    pub fn is_matrix_eq(&mut self, expected: Vec<Vec<T>>) -> &mut Self
+    where
+        T: PartialEq,
+    {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let mismatches = grid_mismatches(&self.actual, &expected, |a, e| a == e);
+        self.handle_execution(Execution {
+            ok: mismatches.is_empty(),
+            log: || format!("assertion failed: `(actual == expectation)`\n{}", render_grids(&self.actual, &expected, &mismatches)),
+            nlog: || format!("assertion failed: `(actual != expectation)`\n     actual: `{:?}`\nexpectation: `{:?}`", self.actual, expected),
+        });
+        self.finish()
+    }
+
+    pub fn is_matrix_approx_eq(&mut self, expected: Vec<Vec<T>>, tolerance: f64) -> &mut Self
+    where
+        T: AsF64,
+    {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let mismatches = grid_mismatches(&self.actual, &expected, |a, e| (a.as_f64() - e.as_f64()).abs() <= tolerance);
+        self.handle_execution(Execution {
+            ok: mismatches.is_empty(),
+            log: || {
+                format!(
+                    "assertion failed: `(actual ≈ expectation)` within `{:?}`\n{}",
+                    tolerance,
+                    render_grids(&self.actual, &expected, &mismatches)
+                )
+            },
+            nlog: || {
+                format!(
+                    "assertion failed: `(actual ≉ expectation)` within `{:?}`\n     actual: `{:?}`\nexpectation: `{:?}`",
+                    tolerance, self.actual, expected
+                )
+            },
+        });
+        self.finish()
+    }
+
+    pub fn row_eq(&mut self, row: usize, expected: &[T]) -> &mut Self
+    where
+        T: PartialEq,
+    {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let actual_row = self.actual.get(row);
+        let ok = matches!(actual_row, Some(r) if r.as_slice() == expected);
+        self.handle_execution(Execution {
+            ok,
+            log: || format!("assertion failed: `(actual[{}] == expectation)`\n     actual: `{:?}`\nexpectation: `{:?}`", row, actual_row, expected),
+            nlog: || format!("assertion failed: `(actual[{}] != expectation)`\n     actual: `{:?}`\nexpectation: `{:?}`", row, actual_row, expected),
+        });
+        self.finish()
+    }
+
+    pub fn column_eq(&mut self, column: usize, expected: &[T]) -> &mut Self
+    where
+        T: PartialEq,
+    {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let actual_column = self.actual.iter().map(|r| r.get(column)).collect::<Vec<_>>();
+        let ok = actual_column.len() == expected.len() && actual_column.iter().zip(expected).all(|(a, e)| *a == Some(e));
+        self.handle_execution(Execution {
+            ok,
+            log: || {
+                format!(
+                    "assertion failed: `(actual[..][{}] == expectation)`\n     actual: `{:?}`\nexpectation: `{:?}`",
+                    column, actual_column, expected
+                )
+            },
+            nlog: || {
+                format!(
+                    "assertion failed: `(actual[..][{}] != expectation)`\n     actual: `{:?}`\nexpectation: `{:?}`",
+                    column, actual_column, expected
+                )
+            },
+        });
+        self.finish()
+    }
+}
+
+fn grid_mismatches<T, F>(actual: &[Vec<T>], expected: &[Vec<T>], eq: F) -> Vec<(usize, usize)>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let mut mismatches = vec![];
+    for row in 0..actual.len().max(expected.len()) {
+        let a_row = actual.get(row).map(Vec::as_slice).unwrap_or_default();
+        let e_row = expected.get(row).map(Vec::as_slice).unwrap_or_default();
+        for column in 0..a_row.len().max(e_row.len()) {
+            match (a_row.get(column), e_row.get(column)) {
+                (Some(a), Some(e)) if eq(a, e) => {}
+                _ => mismatches.push((row, column)),
+            }
+        }
+    }
+    mismatches
+}
+
+fn cell<T: Debug>(grid: &[Vec<T>], row: usize, column: usize) -> Option<String> {
+    grid.get(row).and_then(|r| r.get(column)).map(|c| format!("{:?}", c))
+}
+
+fn render_grids<T: Debug>(actual: &[Vec<T>], expected: &[Vec<T>], mismatches: &[(usize, usize)]) -> String {
+    let rows = actual.len().max(expected.len());
+    let columns = actual.iter().chain(expected.iter()).map(Vec::len).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|c| {
+            (0..rows)
+                .flat_map(|r| vec![cell(actual, r, c), cell(expected, r, c)])
+                .flatten()
+                .map(|s| s.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let render_row = |grid: &[Vec<T>], row: usize| {
+        (0..columns)
+            .map(|c| {
+                let value = cell(grid, row, c).unwrap_or_default();
+                let value = format!("{:>width$}", value, width = widths[c]);
+                if mismatches.contains(&(row, c)) {
+                    format!("[{}]", value)
+                } else {
+                    format!(" {} ", value)
+                }
+            })
+            .collect::<String>()
+    };
+    let left = (0..rows).map(|r| render_row(actual, r)).collect::<Vec<_>>();
+    let left_width = left.iter().map(|l| l.chars().count()).chain(Some("actual".len())).max().unwrap_or(0);
+    let mut lines = vec![format!("{:<width$} | expectation", "actual", width = left_width)];
+    for (row, l) in left.iter().enumerate() {
+        lines.push(format!("{:<width$} | {}", l, render_row(expected, row), width = left_width).trim_end().to_string());
+    }
+    for (row, column) in mismatches {
+        lines.push(format!(
+            "at ({}, {}): {} != {}",
+            row,
+            column,
+            cell(actual, *row, *column).unwrap_or_else(|| "<missing>".to_string()),
+            cell(expected, *row, *column).unwrap_or_else(|| "<missing>".to_string())
+        ));
+    }
+    lines.join("\n")
+}
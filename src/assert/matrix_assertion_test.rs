@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::assert::FailResult;
+    use crate::assert_panic_ignored;
+    use std::panic;
+
+    #[test]
+    fn assert_matrix_should_pass() {
+        let mut assert = assert::new();
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert
+            .that_ref(&grid)
+            .has_dimensions(2, 3)
+            .not()
+            .has_dimensions(3, 2)
+            .row_eq(1, &[4, 5, 6])
+            .column_eq(2, &[3, 6])
+            .not()
+            .column_eq(3, &[3, 6])
+            .is_matrix_eq(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert
+            .that(vec![vec![0.1, 0.2], vec![0.3, 0.4]])
+            .is_matrix_approx_eq(vec![vec![0.1001, 0.2], vec![0.3, 0.3999]], 0.001)
+            .not()
+            .is_matrix_approx_eq(vec![vec![0.1, 0.2], vec![0.3, 0.5]], 0.001);
+        assert.that(vec![vec![1i64, 2], vec![3, 4]]).is_matrix_approx_eq(vec![vec![1, 2], vec![3, 5]], 1.0);
+    }
+
+    #[test]
+    fn assert_matrix_eq_should_fail_with_grid_diff() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+actual     | expectation
+ 1   2  3  |  1   2  3
+ 4 [ 5] 6  |  4 [12] 6
+at (1, 1): 5 != 12"#
+                    );
+                })
+            });
+            assert.that(vec![vec![1, 2, 3], vec![4, 5, 6]]).is_matrix_eq(vec![vec![1, 2, 3], vec![4, 12, 6]]);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_matrix_eq_should_fail_with_missing_cells() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+actual | expectation
+ 1 [2] |  1 [ ]
+[ ][ ] | [3][4]
+at (0, 1): 2 != <missing>
+at (1, 0): <missing> != 3
+at (1, 1): <missing> != 4"#
+                    );
+                })
+            });
+            assert.that(vec![vec![1, 2]]).is_matrix_eq(vec![vec![1], vec![3, 4]]);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_matrix_approx_eq_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual ≈ expectation)` within `0.01`
+actual      | expectation
+ 0.5 [ 1.0] |  0.5 [1.25]
+at (0, 1): 1.0 != 1.25"#
+                    );
+                })
+            });
+            assert.that(vec![vec![0.5, 1.0]]).is_matrix_approx_eq(vec![vec![0.5, 1.25]], 0.01);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_dimensions_should_fail_on_ragged_rows() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual dimensions == expectation)`
+     actual: `2 rows of [2, 3] columns`
+expectation: `2x2`"#
+                    );
+                })
+            });
+            assert.that(vec![vec![1, 2], vec![3, 4, 5]]).has_dimensions(2, 2);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_row_and_column_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert!(
+                        fr.log
+                            == r#"assertion failed: `(actual[1] == expectation)`
+     actual: `Some([3, 4])`
+expectation: `[3, 5]`"# || fr.log
+                            == r#"assertion failed: `(actual[..][0] == expectation)`
+     actual: `[Some(1), Some(3)]`
+expectation: `[1, 4]`"#
+                    );
+                })
+            });
+            assert.that(vec![vec![1, 2], vec![3, 4]]).row_eq(1, &[3, 5]).column_eq(0, &[1, 4]);
+        });
+        assert_panic_ignored!(result)
+    }
+}
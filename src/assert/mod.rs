@@ -1,7 +1,29 @@
 pub mod assertion;
 mod assertion_test;
+pub mod debug_tree;
+mod debug_tree_test;
+pub mod equivalence;
+mod equivalence_test;
+pub mod expression_assertion;
+mod expression_assertion_test;
+pub mod map_assertion;
+mod map_assertion_test;
+pub mod matrix_assertion;
+mod matrix_assertion_test;
+pub mod pattern_assertion;
+mod pattern_assertion_test;
+pub mod stats_assertion;
+mod stats_assertion_test;
+mod table_render;
+mod table_render_test;
+pub mod tree_assertion;
+mod tree_assertion_test;
 pub mod vec_assertion;
 mod vec_assertion_test;
 
 pub use self::assertion::*;
-pub use self::vec_assertion::*;
+pub use self::debug_tree::*;
+pub use self::equivalence::*;
+pub use self::pattern_assertion::*;
+pub use self::stats_assertion::*;
+pub use self::tree_assertion::*;
@@ -0,0 +1,118 @@
+use crate::assert::assertion::Actual;
+use crate::assert::{Execution, Instance, MatcherTrait};
+use std::fmt::Debug;
+
+pub struct PatternMatcher<A: ?Sized> {
+    m_fn: Box<dyn Fn(&A) -> bool>,
+    m_pattern: String,
+}
+
+impl<A: ?Sized> PatternMatcher<A> {
+    pub fn new<F>(m_pattern: &str, m_fn: F) -> Self
+    where
+        F: Fn(&A) -> bool + 'static,
+    {
+        PatternMatcher {
+            m_fn: Box::new(m_fn),
+            m_pattern: m_pattern.to_string(),
+        }
+    }
+}
+
+impl<A> MatcherTrait<A> for PatternMatcher<A>
+where
+    A: Debug + ?Sized,
+{
+    fn matcher_fn(&self, a: &A) -> bool {
+        (self.m_fn)(a)
+    }
+
+    fn log_fn(&self, a: &A) -> String {
+        format!(
+            r#"assertion failed: `(actual matches pattern)`
+     actual: `{:?}`
+    pattern: `{}`"#,
+            a, self.m_pattern
+        )
+    }
+
+    fn nlog_fn(&self, a: &A) -> String {
+        format!(
+            r#"assertion failed: `(actual does not match pattern)`
+     actual: `{:?}`
+    pattern: `{}`"#,
+            a, self.m_pattern
+        )
+    }
+}
+
+type ExtractorFn<A, E> = dyn Fn(&A) -> Option<E>;
+
+pub struct PatternExtractor<A: ?Sized, E> {
+    e_fn: Box<ExtractorFn<A, E>>,
+    e_pattern: String,
+}
+
+impl<A: ?Sized, E> PatternExtractor<A, E> {
+    pub fn new<F>(e_pattern: &str, e_fn: F) -> Self
+    where
+        F: Fn(&A) -> Option<E> + 'static,
+    {
+        PatternExtractor {
+            e_fn: Box::new(e_fn),
+            e_pattern: e_pattern.to_string(),
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! matches_pattern {
+    ($($pattern:pat)|+ $(if $guard:expr)? => $binding:expr) => {
+        $crate::assert::PatternExtractor::new(stringify!($($pattern)|+ $(if $guard)?), move |a| match a {
+            $($pattern)|+ $(if $guard)? => Some($binding),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
+    };
+    ($($pattern:pat)|+ $(if $guard:expr)?) => {
+        $crate::assert::PatternMatcher::new(stringify!($($pattern)|+ $(if $guard)?), move |a| matches!(a, $($pattern)|+ $(if $guard)?))
+    };
+}
+
+impl<A> Instance<'_, A>
+where
+    A: Debug + ?Sized,
+{
+    pub fn bind<'b, E: 'b>(&mut self, extractor: PatternExtractor<A, E>) -> Instance<'b, E> {
+        if self.actual.is_missing() {
+            return self.projected("bind", Actual::Missing);
+        }
+        if self.instance_config.negation {
+            self.handle_execution(Execution {
+                ok: true,
+                log: String::new,
+                nlog: || "bind assertion cannot be negated".to_string(),
+            });
+            self.finish();
+            return self.projected("bind", Actual::Missing);
+        }
+        let bound = (extractor.e_fn)(self.actual.as_ref());
+        let ok = bound.is_some();
+        self.handle_execution(Execution {
+            ok,
+            log: || {
+                format!(
+                    r#"assertion failed: `(actual matches pattern)`
+     actual: `{:?}`
+    pattern: `{}`"#,
+                    self.actual, extractor.e_pattern
+                )
+            },
+            nlog: String::new,
+        });
+        match bound {
+            Some(e) => self.projected("bind", Actual::Owned(Box::new(e))),
+            None => self.projected("bind", Actual::Missing),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::assert::FailResult;
+    use crate::assert_panic_ignored;
+    use crate::matches_pattern;
+    use std::panic;
+
+    #[derive(Debug, PartialEq)]
+    enum Shape {
+        Circle { radius: f64 },
+        Rect(u32, u32),
+        Empty,
+    }
+
+    #[test]
+    fn assert_matches_pattern_should_pass() {
+        let mut assert = assert::new();
+        let min_radius = 1.0;
+        assert.that(Shape::Circle { radius: 2.0 }).do_match(matches_pattern!(Shape::Circle { .. }));
+        assert.that(Shape::Circle { radius: 2.0 }).do_match(matches_pattern!(Shape::Circle { radius } if *radius > min_radius));
+        assert
+            .that(Shape::Circle { radius: 0.5 })
+            .not()
+            .do_match(matches_pattern!(Shape::Circle { radius } if *radius > min_radius));
+        assert.that(Shape::Rect(1, 2)).do_match(matches_pattern!(Shape::Rect(w, h) if w < h));
+        assert.that(Shape::Empty).do_match(matches_pattern!(Shape::Empty | Shape::Rect(0, _)));
+        assert.that(Shape::Empty).not().do_match(matches_pattern!(Shape::Rect(..)));
+        assert.that(vec![Shape::Rect(1, 1), Shape::Rect(2, 3)]).all(matches_pattern!(Shape::Rect(..)));
+        assert.that(vec![Shape::Empty, Shape::Rect(2, 3)]).any(matches_pattern!(Shape::Rect(2, _)));
+    }
+
+    #[test]
+    fn assert_matches_pattern_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual matches pattern)`
+     actual: `Rect(1, 2)`
+    pattern: `Shape::Circle { .. }`"#
+                    );
+                })
+            });
+            assert.that(Shape::Rect(1, 2)).do_match(matches_pattern!(Shape::Circle { .. }));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_matches_pattern_should_fail_on_guard() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual matches pattern)`
+     actual: `Circle { radius: 0.5 }`
+    pattern: `Shape::Circle { radius } if *radius > 1.0`"#
+                    );
+                })
+            });
+            assert.that(Shape::Circle { radius: 0.5 }).do_match(matches_pattern!(Shape::Circle { radius } if *radius > 1.0));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_matches_pattern_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual does not match pattern)`
+     actual: `Empty`
+    pattern: `Shape::Empty`"#
+                    );
+                })
+            });
+            assert.that(Shape::Empty).not().do_match(matches_pattern!(Shape::Empty));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_bind_should_pass() {
+        let mut assert = assert::new();
+        assert.that(Shape::Circle { radius: 2.0 }).bind(matches_pattern!(Shape::Circle { radius } => *radius)).is_eq(2.0);
+        assert.that(Shape::Rect(2, 3)).bind(matches_pattern!(Shape::Rect(w, h) if w < h => vec![*w, *h])).has_len(2);
+        assert.that(Some(Shape::Empty)).bind(matches_pattern!(Some(s) => format!("{:?}", s))).is_eq(String::from("Empty"));
+    }
+
+    #[test]
+    fn assert_bind_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual matches pattern)`
+     actual: `Rect(3, 2)`
+    pattern: `Shape::Rect(w, h) if w < h`"#
+                    );
+                })
+            });
+            assert.that(Shape::Rect(3, 2)).bind(matches_pattern!(Shape::Rect(w, h) if w < h => *w)).is_eq(3).not().is_eq(3);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_bind_should_record_failures_on_bound_value() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+     actual: `2.0`
+expectation: `3.0`
+ projection: `actual.bind(..)`"#
+                    );
+                })
+            });
+            assert.that(Shape::Circle { radius: 2.0 }).bind(matches_pattern!(Shape::Circle { radius } => *radius)).is_eq(3.0);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_bind_should_prevent_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "bind assertion cannot be negated");
+                })
+            });
+            assert.that(Shape::Empty).not().bind(matches_pattern!(Shape::Empty => 1)).is_eq(2);
+        });
+        assert_panic_ignored!(result)
+    }
+}
@@ -0,0 +1,205 @@
+use crate::assert::{Execution, Instance};
+use std::fmt::Debug;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Epsilon {
+    Absolute(f64),
+    Relative(f64),
+}
+
+impl Epsilon {
+    fn error(&self, a: f64, e: f64) -> f64 {
+        match self {
+            Epsilon::Absolute(_) => (a - e).abs(),
+            Epsilon::Relative(_) if a == e => 0.0,
+            Epsilon::Relative(_) => (a - e).abs() / a.abs().max(e.abs()),
+        }
+    }
+
+    fn accepts(&self, error: f64) -> bool {
+        match self {
+            Epsilon::Absolute(epsilon) | Epsilon::Relative(epsilon) => error <= *epsilon,
+        }
+    }
+}
+
+// Statistics are computed on `f64`, large integers may lose precision.
+pub trait AsF64: Copy {
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! impl_as_f64 {
+    ($($t:ty),*) => {
+        $(impl AsF64 for $t {
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_as_f64!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T> Instance<'_, Vec<T>>
+where
+    T: AsF64 + Debug,
+{
+    pub fn has_sum(&mut self, expected: f64, tolerance: f64) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let sum = self.values().iter().sum();
+        self.has_statistic("sum()", sum, None, expected, tolerance)
+    }
+
+    pub fn has_mean(&mut self, expected: f64, tolerance: f64) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let mean = mean(&self.values());
+        self.has_statistic("mean()", mean, None, expected, tolerance)
+    }
+
+    pub fn has_median(&mut self, expected: f64, tolerance: f64) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let (median, worst) = self.percentile(50.0);
+        self.has_statistic("median()", median, worst, expected, tolerance)
+    }
+
+    pub fn has_percentile(&mut self, percentile: f64, expected: f64, tolerance: f64) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let (value, worst) = self.percentile(percentile);
+        self.has_statistic(&format!("percentile({:?})", percentile), value, worst, expected, tolerance)
+    }
+
+    pub fn has_min(&mut self, expected: f64, tolerance: f64) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let values = self.values();
+        let worst = (0..values.len()).min_by(|i, j| values[*i].total_cmp(&values[*j]));
+        let min = worst.map_or(f64::NAN, |i| values[i]);
+        self.has_statistic("min()", min, worst, expected, tolerance)
+    }
+
+    pub fn has_max(&mut self, expected: f64, tolerance: f64) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let values = self.values();
+        let worst = (0..values.len()).max_by(|i, j| values[*i].total_cmp(&values[*j]));
+        let max = worst.map_or(f64::NAN, |i| values[i]);
+        self.has_statistic("max()", max, worst, expected, tolerance)
+    }
+
+    pub fn has_stddev(&mut self, expected: f64, tolerance: f64) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let values = self.values();
+        let mean = mean(&values);
+        let stddev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt();
+        self.has_statistic("stddev()", stddev, None, expected, tolerance)
+    }
+
+    pub fn is_approx_eq(&mut self, expected: &[f64], epsilon: Epsilon) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let values = self.values();
+        // Values of different lengths are never approximately equal, which is what a negated assertion expects.
+        if values.len() != expected.len() {
+            if !self.instance_config.negation {
+                self.handle_failure(|| "expectation length is different from input length".to_string());
+            }
+            return self.finish();
+        }
+        let errors = values.iter().zip(expected).map(|(a, e)| epsilon.error(*a, *e)).collect::<Vec<_>>();
+        let worst = (0..errors.len()).max_by(|i, j| errors[*i].total_cmp(&errors[*j]));
+        let ok = errors.iter().all(|e| epsilon.accepts(*e));
+        let worst_line = || match worst {
+            Some(i) => format!("\n      worst: `actual[{}] = {:?}, expectation[{}] = {:?}, error = {:?}`", i, self.actual[i], i, expected[i], errors[i]),
+            None => "".to_string(),
+        };
+        self.handle_execution(Execution {
+            ok,
+            log: || {
+                format!(
+                    "assertion failed: `(actual ≈ expectation)` within `{:?}`\n     actual: `{:?}`\nexpectation: `{:?}`{}",
+                    epsilon,
+                    self.actual,
+                    expected,
+                    worst_line()
+                )
+            },
+            nlog: || {
+                format!(
+                    "assertion failed: `(actual ≉ expectation)` within `{:?}`\n     actual: `{:?}`\nexpectation: `{:?}`{}",
+                    epsilon,
+                    self.actual,
+                    expected,
+                    worst_line()
+                )
+            },
+        });
+        self.finish()
+    }
+
+    fn values(&self) -> Vec<f64> {
+        self.actual.iter().map(|v| v.as_f64()).collect()
+    }
+
+    fn percentile(&self, percentile: f64) -> (f64, Option<usize>) {
+        let values = self.values();
+        let mut sorted = (0..values.len()).collect::<Vec<_>>();
+        sorted.sort_by(|i, j| values[*i].total_cmp(&values[*j]));
+        if sorted.is_empty() {
+            return (f64::NAN, None);
+        }
+        let rank = percentile.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+        let (low, high) = (values[sorted[rank.floor() as usize]], values[sorted[rank.ceil() as usize]]);
+        (low + (high - low) * rank.fract(), Some(sorted[rank.round() as usize]))
+    }
+
+    // Aggregates such as the sum, mean or stddev have no single worst value, so they pass `None`.
+    fn has_statistic(&mut self, name: &str, statistic: f64, worst: Option<usize>, expected: f64, tolerance: f64) -> &mut Self {
+        let worst_line = || match worst {
+            Some(i) => format!("\n      worst: `actual[{}] = {:?}`", i, self.actual[i]),
+            None => "".to_string(),
+        };
+        self.handle_execution(Execution {
+            ok: (statistic - expected).abs() <= tolerance,
+            log: || {
+                format!(
+                    "assertion failed: `(actual.{} ≈ expectation)` within `{:?}`\n     actual.{}: `{:?}`\nexpectation: `{:?}`{}",
+                    name,
+                    tolerance,
+                    name,
+                    statistic,
+                    expected,
+                    worst_line()
+                )
+            },
+            nlog: || {
+                format!(
+                    "assertion failed: `(actual.{} ≉ expectation)` within `{:?}`\n     actual.{}: `{:?}`\nexpectation: `{:?}`{}",
+                    name,
+                    tolerance,
+                    name,
+                    statistic,
+                    expected,
+                    worst_line()
+                )
+            },
+        });
+        self.finish()
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::assert::{Epsilon, FailResult};
+    use crate::assert_panic_ignored;
+    use std::panic;
+
+    #[test]
+    fn assert_statistics_should_pass() {
+        let mut assert = assert::new();
+        assert
+            .that(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0])
+            .has_sum(40.0, 0.0)
+            .has_mean(5.0, 0.0)
+            .has_median(4.5, 0.0)
+            .has_min(2.0, 0.0)
+            .has_max(9.0, 0.0)
+            .has_stddev(2.0, 1e-9)
+            .has_percentile(0.0, 2.0, 0.0)
+            .has_percentile(100.0, 9.0, 0.0)
+            .has_percentile(75.0, 5.5, 1e-9)
+            .not()
+            .has_mean(6.0, 0.5);
+        assert.that(vec![3, 1, 2]).has_sum(6.0, 0.0).has_median(2.0, 0.0).has_max(3.0, 0.0);
+        assert.that(vec![3i64, 1, 2]).has_mean(2.0, 0.0);
+        assert.that(vec![3u64, 1, 2]).has_min(1.0, 0.0);
+        assert.that(vec![3usize, 1, 2]).has_stddev((2.0f64 / 3.0).sqrt(), 1e-9);
+        assert.that(vec![-3isize, 1, 2]).has_sum(0.0, 0.0);
+    }
+
+    #[test]
+    fn assert_statistic_should_fail_with_worst_index() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual.max() ≈ expectation)` within `0.1`
+     actual.max(): `10.0`
+expectation: `2.0`
+      worst: `actual[3] = 10`"#
+                    );
+                })
+            });
+            assert.that(vec![2, 2, 2, 10]).has_max(2.0, 0.1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_aggregate_statistic_should_fail_without_worst_index() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual.mean() ≈ expectation)` within `0.1`
+     actual.mean(): `4.0`
+expectation: `2.0`"#
+                    );
+                })
+            });
+            assert.that(vec![2, 2, 2, 10]).has_mean(2.0, 0.1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_statistic_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual.percentile(90.0) ≉ expectation)` within `0.5`
+     actual.percentile(90.0): `9.1`
+expectation: `9.0`
+      worst: `actual[8] = 9.0`"#
+                    );
+                })
+            });
+            assert.that((1..=10).map(f64::from).collect::<Vec<_>>()).not().has_percentile(90.0, 9.0, 0.5);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_statistic_should_fail_when_empty() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual.min() ≈ expectation)` within `0.0`
+     actual.min(): `NaN`
+expectation: `0.0`"#
+                    );
+                })
+            });
+            assert.that(Vec::<f64>::new()).has_min(0.0, 0.0);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_approx_eq_should_pass() {
+        let mut assert = assert::new();
+        assert
+            .that(vec![1.0, 100.0])
+            .is_approx_eq(&[1.001, 100.001], Epsilon::Absolute(0.01))
+            .is_approx_eq(&[1.0, 101.0], Epsilon::Relative(0.01))
+            .not()
+            .is_approx_eq(&[1.0, 101.0], Epsilon::Absolute(0.01));
+    }
+
+    #[test]
+    fn assert_approx_eq_should_fail_with_worst_index() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual ≈ expectation)` within `Relative(0.1)`
+     actual: `[1.0, 2.0, 4.0]`
+expectation: `[1.05, 2.5, 4.0]`
+      worst: `actual[1] = 2.0, expectation[1] = 2.5, error = 0.2`"#
+                    );
+                })
+            });
+            assert.that(vec![1.0, 2.0, 4.0]).is_approx_eq(&[1.05, 2.5, 4.0], Epsilon::Relative(0.1));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_approx_eq_should_fail_when_different_length() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "expectation length is different from input length");
+                })
+            });
+            assert.that(vec![1.0]).is_approx_eq(&[1.0, 2.0], Epsilon::Absolute(0.1));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_not_approx_eq_should_pass_when_different_length() {
+        let mut assert = assert::new();
+        assert
+            .that(vec![1.0])
+            .not()
+            .is_approx_eq(&[1.0, 2.0], Epsilon::Absolute(0.1))
+            .is_approx_eq(&[1.0], Epsilon::Absolute(0.1));
+    }
+}
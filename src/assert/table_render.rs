@@ -0,0 +1,98 @@
+use crate::assert::DebugValue;
+
+const MAX_TABLE_ROWS: usize = 20;
+
+pub(super) struct FailedRow {
+    pub(super) position: usize,
+    pub(super) value: Option<String>,
+    pub(super) explanation: String,
+}
+
+pub(super) fn render_failure_table(total: usize, rows: &[FailedRow]) -> Option<String> {
+    if rows.is_empty() {
+        return None;
+    }
+    let records = rows.iter().map(|r| record_columns(r.value.as_deref()?)).collect::<Option<Vec<_>>>()?;
+    let mut columns: Vec<&str> = vec![];
+    for (column, _) in records.iter().flatten() {
+        if !columns.contains(&column.as_str()) {
+            columns.push(column);
+        }
+    }
+
+    let mut table = vec![Some("position")
+        .into_iter()
+        .chain(columns.iter().cloned())
+        .chain(Some("explanation"))
+        .map(String::from)
+        .collect::<Vec<_>>()];
+    for (row, record) in rows.iter().zip(records.iter()).take(MAX_TABLE_ROWS) {
+        let explanation = row.explanation.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+        let mut cells = vec![row.position.to_string()];
+        for column in columns.iter() {
+            let value = record.iter().find(|(c, _)| c == column).map(|(_, v)| v.as_str()).unwrap_or_default();
+            if mentions_field(&explanation, column) {
+                cells.push(format!("[{}]", value));
+            } else {
+                cells.push(value.to_string());
+            }
+        }
+        cells.push(explanation);
+        table.push(cells);
+    }
+
+    let widths = (0..table[0].len()).map(|c| table.iter().map(|r| r[c].chars().count()).max().unwrap_or(0)).collect::<Vec<_>>();
+    let mut lines = vec![format!("assertion failed: `({} of {} items failed)`", rows.len(), total)];
+    for cells in table.iter() {
+        let line = cells
+            .iter()
+            .enumerate()
+            .map(|(c, cell)| {
+                if c == 0 {
+                    format!("{:>width$}", cell, width = widths[c])
+                } else {
+                    format!("{:<width$}", cell, width = widths[c])
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        lines.push(line.trim_end().to_string());
+    }
+    if rows.len() > MAX_TABLE_ROWS {
+        lines.push(format!("... {} more failing items not shown", rows.len() - MAX_TABLE_ROWS));
+    }
+    Some(lines.join("\n"))
+}
+
+// Used when the items cannot be rendered as a table, one failure per row with the same cap.
+pub(super) fn render_failure_lines(rows: &[FailedRow]) -> Vec<String> {
+    let mut lines = rows.iter().take(MAX_TABLE_ROWS).map(|r| format!("{} - at position {}", r.explanation, r.position)).collect::<Vec<_>>();
+    if rows.len() > MAX_TABLE_ROWS {
+        lines.push(format!("... {} more failing items not shown", rows.len() - MAX_TABLE_ROWS));
+    }
+    lines
+}
+
+fn record_columns(repr: &str) -> Option<Vec<(String, String)>> {
+    match DebugValue::parse(repr)? {
+        DebugValue::Struct { fields, .. } => Some(fields.into_iter().map(|(name, value)| (name, value.to_string())).collect()),
+        DebugValue::Tuple { items, .. } => Some(items.iter().enumerate().map(|(i, value)| (i.to_string(), value.to_string())).collect()),
+        _ => None,
+    }
+}
+
+// A column is bracketed as the offending one when the explanation reads it as `.field` or binds it as `field:`
+// in a pattern. This is a textual guess on the matcher description: a field named in any other way is not
+// bracketed, and `field::` paths or longer identifiers containing the name are not taken as mentions.
+fn mentions_field(explanation: &str, field: &str) -> bool {
+    let is_ident = |c: Option<char>| matches!(c, Some(c) if c.is_alphanumeric() || c == '_');
+    explanation.match_indices(field).any(|(i, _)| {
+        let before = explanation[..i].chars().next_back();
+        let mut after = explanation[i + field.len()..].chars();
+        let next = after.next();
+        if is_ident(before) || is_ident(next) {
+            return false;
+        }
+        before == Some('.') || (next == Some(':') && after.next() != Some(':'))
+    })
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert::table_render::{render_failure_lines, render_failure_table, FailedRow};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Order {
+        id: u32,
+        label: String,
+        price: i32,
+    }
+
+    fn failed(position: usize, order: Order, explanation: &str) -> FailedRow {
+        FailedRow {
+            position,
+            value: Some(format!("{:?}", order)),
+            explanation: explanation.to_string(),
+        }
+    }
+
+    #[test]
+    fn render_failure_table_should_align_rows_and_highlight_columns() {
+        let rows = vec![
+            failed(
+                1,
+                Order {
+                    id: 2,
+                    label: "b".to_string(),
+                    price: -3,
+                },
+                "assertion failed: `(matcher \"&|o| o.price > 0\" failed)`",
+            ),
+            failed(
+                12,
+                Order {
+                    id: 13,
+                    label: "long label".to_string(),
+                    price: 0,
+                },
+                "assertion failed: `(matcher \"&|o| o.price > 0\" failed)`",
+            ),
+        ];
+        assert_eq!(
+            render_failure_table(20, &rows).unwrap(),
+            r#"assertion failed: `(2 of 20 items failed)`
+position | id | label        | price | explanation
+       1 | 2  | "b"          | [-3]  | assertion failed: `(matcher "&|o| o.price > 0" failed)`
+      12 | 13 | "long label" | [0]   | assertion failed: `(matcher "&|o| o.price > 0" failed)`"#
+        );
+    }
+
+    #[test]
+    fn render_failure_table_should_highlight_pattern_fields() {
+        let rows = vec![failed(
+            0,
+            Order {
+                id: 1,
+                label: "a".to_string(),
+                price: 1,
+            },
+            "assertion failed: `(actual matches pattern)`\n     actual: `..`\n    pattern: `Order { id: 2, .. }`",
+        )];
+        assert_eq!(
+            render_failure_table(1, &rows).unwrap(),
+            r#"assertion failed: `(1 of 1 items failed)`
+position | id  | label | price | explanation
+       0 | [1] | "a"   | 1     | assertion failed: `(actual matches pattern)` actual: `..` pattern: `Order { id: 2, .. }`"#
+        );
+    }
+
+    #[test]
+    fn render_failure_table_should_cap_rows() {
+        let rows = (0..100)
+            .map(|i| {
+                failed(
+                    i,
+                    Order {
+                        id: i as u32,
+                        label: "x".to_string(),
+                        price: 0,
+                    },
+                    "failed",
+                )
+            })
+            .collect::<Vec<_>>();
+        let table = render_failure_table(100, &rows).unwrap();
+        assert_eq!(table.lines().count(), 23);
+        assert!(table.starts_with("assertion failed: `(100 of 100 items failed)`"));
+        assert!(table.ends_with("... 80 more failing items not shown"));
+    }
+
+    #[test]
+    fn render_failure_lines_should_cap_rows() {
+        let rows = (0..30)
+            .map(|i| FailedRow {
+                position: i,
+                value: None,
+                explanation: "failed".to_string(),
+            })
+            .collect::<Vec<_>>();
+        let lines = render_failure_lines(&rows);
+        assert_eq!(lines.len(), 21);
+        assert_eq!(lines[19], "failed - at position 19");
+        assert_eq!(lines[20], "... 10 more failing items not shown");
+        assert_eq!(render_failure_lines(&rows[..2]), vec!["failed - at position 0", "failed - at position 1"]);
+    }
+
+    #[test]
+    fn render_failure_table_should_skip_non_record_values() {
+        let rows = vec![FailedRow {
+            position: 0,
+            value: Some("3".to_string()),
+            explanation: "failed".to_string(),
+        }];
+        assert!(render_failure_table(1, &rows).is_none());
+        assert!(render_failure_table(1, &[]).is_none());
+    }
+}
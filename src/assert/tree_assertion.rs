@@ -0,0 +1,187 @@
+use crate::assert::assertion::{Actual, InstanceConfig};
+use crate::assert::{Assertion, Execution, Instance};
+use crate::fs::tree_diff::{content_diff, display_path};
+use crate::fs::{ContentDiff, FileNode, FsTestError, NodeKind, TmpTestFolder, TreeChange};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+pub trait FileTreeSource {
+    fn file_tree(&self) -> Result<Cow<'_, FileNode>, FsTestError>;
+}
+
+impl FileTreeSource for FileNode {
+    fn file_tree(&self) -> Result<Cow<'_, FileNode>, FsTestError> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl FileTreeSource for TmpTestFolder {
+    fn file_tree(&self) -> Result<Cow<'_, FileNode>, FsTestError> {
+        Ok(Cow::Owned(self.read()?))
+    }
+}
+
+impl FileTreeSource for Path {
+    fn file_tree(&self) -> Result<Cow<'_, FileNode>, FsTestError> {
+        Ok(Cow::Owned(FileNode::new_from_path(self)?))
+    }
+}
+
+impl FileTreeSource for PathBuf {
+    fn file_tree(&self) -> Result<Cow<'_, FileNode>, FsTestError> {
+        self.as_path().file_tree()
+    }
+}
+
+impl Assertion {
+    // The source is read once here, chained assertions all check the same tree.
+    pub fn that_tree<'a, S: FileTreeSource + ?Sized>(&mut self, source: &'a S) -> Instance<'a, FileNode> {
+        let (actual, error) = match source.file_tree() {
+            Ok(Cow::Borrowed(tree)) => (Actual::Borrowed(tree), None),
+            Ok(Cow::Owned(tree)) => (Actual::Owned(Box::new(tree)), None),
+            Err(e) => (Actual::Missing, Some(e)),
+        };
+        let instance = Instance {
+            parent: Rc::clone(&self.rca),
+            actual,
+            instance_config: InstanceConfig::default(),
+        };
+        if let Some(e) = error {
+            instance.handle_failure(|| format!("assertion failed: `(actual tree is readable)`\n      error: `{}`", e));
+        }
+        instance
+    }
+}
+
+impl Instance<'_, FileNode> {
+    pub fn matches_tree(&mut self, expected: &FileNode) -> &mut Self {
+        self.tree_changes(expected, true)
+    }
+
+    pub fn contains_tree(&mut self, expected: &FileNode) -> &mut Self {
+        self.tree_changes(expected, false)
+    }
+
+    pub fn has_file<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.has_kind(path.as_ref(), NodeKind::File)
+    }
+
+    pub fn has_dir<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.has_kind(path.as_ref(), NodeKind::Dir)
+    }
+
+    pub fn file_content_eq<P: AsRef<Path>>(&mut self, path: P, expected: &[u8]) -> &mut Self {
+        let path = path.as_ref();
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let tree = &self.actual;
+        let node = tree.get(path);
+        let ok = match node {
+            Some(FileNode::File { content, .. }) => content == expected,
+            Some(digest @ FileNode::FileDigest { .. }) => digest.same_content(&FileNode::new_file(digest.get_name(), expected.to_vec())),
+            _ => false,
+        };
+        let content_line = || match node {
+            Some(FileNode::File { content, .. }) => format!("\n{}", content_lines(&content_diff(content, expected))),
+            Some(FileNode::FileDigest { size, .. }) => format!(
+                "\n{}",
+                content_lines(&ContentDiff::Digest {
+                    size: *size,
+                    other_size: expected.len() as u64
+                })
+            ),
+            _ => format!("\n     actual: `{}`", describe(node.map(FileNode::kind))),
+        };
+        self.handle_execution(Execution {
+            ok,
+            log: || format!("assertion failed: `(actual content == expectation)`\n       path: `{}`{}", display_path(path), content_line()),
+            nlog: || format!("assertion failed: `(actual content != expectation)`\n       path: `{}`", display_path(path)),
+        });
+        self.finish()
+    }
+
+    pub fn has_entry_count(&mut self, expected: usize) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let tree = &self.actual;
+        let count = tree.walk().count();
+        self.handle_execution(Execution {
+            ok: count == expected,
+            log: || format!("assertion failed: `(actual entry count == expectation)`\n     actual: `{}`\nexpectation: `{}`", count, expected),
+            nlog: || format!("assertion failed: `(actual entry count != expectation)`\n     actual: `{}`\nexpectation: `{}`", count, expected),
+        });
+        self.finish()
+    }
+
+    fn tree_changes(&mut self, expected: &FileNode, exact: bool) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let tree = &self.actual;
+        let (op, nop) = if exact { ("matches", "does not match") } else { ("contains", "does not contain") };
+        let changes = tree
+            .diff(expected)
+            .changes
+            .into_iter()
+            .filter(|c| exact || !matches!(c, TreeChange::Removed { .. }))
+            .collect::<Vec<_>>();
+        if self.instance_config.negation {
+            self.handle_execution(Execution {
+                ok: changes.is_empty(),
+                log: String::new,
+                nlog: || format!("assertion failed: `(actual tree {} expectation)`", nop),
+            });
+            return self.finish();
+        }
+        for change in changes {
+            self.handle_failure(|| format!("assertion failed: `(actual tree {} expectation)`\n{}", op, change_lines(&change)));
+        }
+        self.finish()
+    }
+
+    fn has_kind(&mut self, path: &Path, expected: NodeKind) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let tree = &self.actual;
+        let actual = tree.get(path).map(FileNode::kind);
+        self.handle_execution(Execution {
+            ok: actual == Some(expected),
+            log: || {
+                format!(
+                    "assertion failed: `(actual has {})`\n       path: `{}`\n     actual: `{}`",
+                    expected,
+                    display_path(path),
+                    describe(actual)
+                )
+            },
+            nlog: || format!("assertion failed: `(actual has no {})`\n       path: `{}`", expected, display_path(path)),
+        });
+        self.finish()
+    }
+}
+
+fn describe(kind: Option<NodeKind>) -> String {
+    kind.map_or_else(|| "<missing>".to_string(), |k| k.to_string())
+}
+
+fn change_lines(change: &TreeChange) -> String {
+    let labelled = |path: &Path, actual: String, expected: String| format!("       path: `{}`\n     actual: `{}`\nexpectation: `{}`", display_path(path), actual, expected);
+    match change {
+        TreeChange::Added { path, kind } => labelled(path, describe(None), kind.to_string()),
+        TreeChange::Removed { path, kind } => labelled(path, kind.to_string(), describe(None)),
+        TreeChange::TypeChanged { path, kind, other_kind } => labelled(path, kind.to_string(), other_kind.to_string()),
+        TreeChange::TargetChanged { path, target, other_target } => labelled(path, target.display().to_string(), other_target.display().to_string()),
+        TreeChange::Modified { path, content } => format!("       path: `{}`\n{}", display_path(path), content_lines(content)),
+    }
+}
+
+fn content_lines(content: &ContentDiff) -> String {
+    match content {
+        ContentDiff::Text(_) => content.to_string(),
+        ContentDiff::LargeText { .. } | ContentDiff::Binary { .. } | ContentDiff::LineEndings { .. } | ContentDiff::Digest { .. } => format!("       diff: `{}`", content),
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::assert;
+    use crate::assert::FailResult;
+    use crate::assert_panic_ignored;
+    use crate::fs::{FileNode, TmpTestFolder};
+    use std::fs;
+    use std::panic;
+    use std::path::Path;
+
+    fn text(name: &str, content: &str) -> FileNode {
+        FileNode::new_file(name, content.as_bytes().to_vec())
+    }
+
+    fn dir(name: &str, sub: Vec<FileNode>) -> FileNode {
+        FileNode::Dir { name: name.into(), sub, mode: None }
+    }
+
+    fn tree() -> FileNode {
+        dir("root", vec![text("a.txt", "a\nb"), dir("d", vec![text("f", "content"), dir("e", vec![])])])
+    }
+
+    #[test]
+    fn assert_tree_should_pass() {
+        let mut assert = assert::new();
+        let folder = TmpTestFolder::new_from_node(&tree()).unwrap();
+        let expected = dir("expected", vec![tree()]);
+        let subset = dir("expected", vec![dir("root", vec![dir("d", vec![text("f", "content")])])]);
+        assert.that(dir("expected", vec![tree()])).matches_tree(&expected).contains_tree(&subset);
+        assert.that_tree(&folder).matches_tree(&expected).not().matches_tree(&subset).contains_tree(&subset);
+        assert
+            .that_tree(folder.get_path())
+            .has_file("root/a.txt")
+            .has_dir("root/d/e")
+            .not()
+            .has_file("root/d")
+            .not()
+            .has_dir("missing")
+            .file_content_eq("root/d/f", b"content")
+            .has_entry_count(5);
+        assert.that(tree()).not().contains_tree(&expected).not().has_entry_count(3);
+    }
+
+    #[test]
+    fn assert_matches_tree_should_fail_for_each_change() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert!(
+                        [
+                            "assertion failed: `(actual tree matches expectation)`\n       path: `a.txt`\n  -2: b\n  +2: B",
+                            "assertion failed: `(actual tree matches expectation)`\n       path: `d/e`\n     actual: `dir`\nexpectation: `file`",
+                            "assertion failed: `(actual tree matches expectation)`\n       path: `d/f`\n     actual: `file`\nexpectation: `<missing>`",
+                            "assertion failed: `(actual tree matches expectation)`\n       path: `g`\n     actual: `<missing>`\nexpectation: `file`",
+                        ]
+                        .contains(&fr.log.as_str()),
+                        "{}",
+                        fr.log
+                    );
+                })
+            });
+            let expected = dir("root", vec![text("a.txt", "a\nB"), dir("d", vec![text("e", "")]), text("g", "")]);
+            assert.that(tree()).matches_tree(&expected);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_contains_tree_should_fail_on_binary_content() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        "assertion failed: `(actual tree contains expectation)`\n       path: `bin`\n       diff: `binary, 3 != 3 bytes, first difference at offset 1`"
+                    );
+                })
+            });
+            let actual = dir("root", vec![FileNode::new_file("bin", vec![0, 1, 2]), text("other", "")]);
+            assert.that(actual).contains_tree(&dir("root", vec![FileNode::new_file("bin", vec![0, 9, 2])]));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_contains_tree_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(actual tree does not contain expectation)`");
+                })
+            });
+            assert.that(tree()).not().contains_tree(&dir("root", vec![]));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_has_file_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(actual has file)`\n       path: `d/e`\n     actual: `dir`");
+                })
+            });
+            assert.that(tree()).has_file("d/e");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_has_dir_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(actual has no dir)`\n       path: `d`");
+                })
+            });
+            assert.that(tree()).not().has_dir("d");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_file_content_eq_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert!(
+                        [
+                            "assertion failed: `(actual content == expectation)`\n       path: `a.txt`\n  -1: a\n  +1: c",
+                            "assertion failed: `(actual content == expectation)`\n       path: `d/g`\n     actual: `<missing>`",
+                        ]
+                        .contains(&fr.log.as_str()),
+                        "{}",
+                        fr.log
+                    );
+                })
+            });
+            assert.that(tree()).file_content_eq("a.txt", b"c\nb").file_content_eq("d/g", b"");
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_has_entry_count_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(actual entry count == expectation)`\n     actual: `4`\nexpectation: `3`");
+                })
+            });
+            assert.that(tree()).has_entry_count(3);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_tree_should_read_source_once() {
+        let mut assert = assert::new();
+        let folder = TmpTestFolder::new_from_node(&tree()).unwrap();
+        let mut instance = assert.that_tree(&folder);
+        fs::remove_file(folder.get_path().join("root").join("a.txt")).unwrap();
+        instance.has_file("root/a.txt").has_entry_count(5);
+        assert.that_tree(&folder.get_path().join("root")).not().has_file("a.txt").has_dir("d");
+    }
+
+    #[test]
+    fn assert_tree_should_fail_on_unreadable_path() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert!(fr.log.starts_with("assertion failed: `(actual tree is readable)`\n      error: `"), "{}", fr.log);
+                })
+            });
+            assert.that_tree(Path::new("/does/not/exist")).has_file("f").not().has_file("f");
+        });
+        assert_panic_ignored!(result)
+    }
+}
@@ -1,175 +1,407 @@
-use crate::assert::{Execution, Instance, MatcherTrait};
-use std::borrow::Borrow;
-use std::rc::Rc;
+use crate::assert::assertion::Actual;
+use crate::assert::equivalence::expectation_eq;
+use crate::assert::table_render::{render_failure_lines, render_failure_table, FailedRow};
+use crate::assert::{EqExpectation, Execution, Instance, MatcherTrait};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
 
-impl<A> Instance<Vec<A>>
-where
-    A: PartialEq,
-{
-    pub fn contains<E>(&mut self, expected: E)
+impl<A> Instance<'_, Vec<A>> {
+    pub fn contains<E, M>(&mut self, expected: E) -> &mut Self
     where
-        E: Borrow<A>,
+        E: EqExpectation<A, M>,
     {
-        let ok = matches!(self.actual.iter().find(|a| &expected.borrow() == a), Some(_));
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let ok = {
+            let parent = RefCell::borrow(&self.parent);
+            self.actual.iter().any(|a| expectation_eq(&parent.equivalences, a, &expected))
+        };
         self.handle_execution(Execution {
             ok,
-            log: "assertion failed: `(expectation ∈ actual)`".to_string(),
-            nlog: "assertion failed: `(expectation ∉ actual)`".to_string(),
+            log: || "assertion failed: `(expectation ∈ actual)`".to_string(),
+            nlog: || "assertion failed: `(expectation ∉ actual)`".to_string(),
         });
+        self.finish()
     }
 
-    pub fn eq_each<E>(&mut self, expected: &[E])
+    pub fn eq_each<E, M>(&mut self, expected: &[E]) -> &mut Self
     where
-        E: Borrow<A>,
+        E: EqExpectation<A, M>,
     {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
         if self.instance_config.negation {
             self.handle_execution(Execution {
                 ok: true,
-                log: "".to_string(),
-                nlog: "eq_each assertion cannot be negated".to_string(),
+                log: String::new,
+                nlog: || "eq_each assertion cannot be negated".to_string(),
             });
-            return;
+            return self.finish();
         }
         if self.actual.len() != expected.len() {
             let log = "expectation length is different from input length";
             self.handle_execution(Execution {
                 ok: false,
-                log: log.to_string(),
-                nlog: "".to_string(),
+                log: || log.to_string(),
+                nlog: String::new,
             });
-            return;
+            return self.finish();
         }
-        for pos in 0..self.actual.len() {
-            let a = self.actual.get(pos).unwrap();
-            let ok = matches!(expected.get(pos), Some(e) if e.borrow() == a);
+        let oks: Vec<bool> = {
+            let parent = RefCell::borrow(&self.parent);
+            self.actual.iter().zip(expected).map(|(a, e)| expectation_eq(&parent.equivalences, a, e)).collect()
+        };
+        for (pos, ok) in oks.into_iter().enumerate() {
             self.handle_execution(Execution {
                 ok,
-                log: format!("assertion failed: `(expectation[{}] = actual[{}])`", pos, pos),
-                nlog: "".to_string(),
+                log: || format!("assertion failed: `(expectation[{}] = actual[{}])`", pos, pos),
+                nlog: String::new,
             });
         }
+        self.finish()
     }
 }
 
-impl<A> Instance<Vec<A>> {
-    pub fn map<E, T: FnMut(&A) -> E>(&mut self, mapper: T) -> Instance<Vec<E>> {
+impl<A> Instance<'_, Vec<A>> {
+    pub fn map<'b, E: 'b, T: FnMut(&A) -> E>(&mut self, mapper: T) -> Instance<'b, Vec<E>> {
+        if self.actual.is_missing() {
+            return self.projected("map", Actual::Missing);
+        }
         let actual = self.actual.iter().map(mapper).collect::<Vec<_>>();
-        Instance {
-            parent: Rc::clone(&self.parent),
-            actual: Box::new(actual),
-            instance_config: self.instance_config.clone(),
+        self.projected("map", Actual::Owned(Box::new(actual)))
+    }
+
+    pub fn map_ref<E: ?Sized, T: Fn(&A) -> &E>(&self, mapper: T) -> Instance<'_, Vec<&E>> {
+        if self.actual.is_missing() {
+            return self.projected("map_ref", Actual::Missing);
         }
+        let actual = self.actual.iter().map(mapper).collect::<Vec<_>>();
+        self.projected("map_ref", Actual::Owned(Box::new(actual)))
     }
 
-    pub fn has_len(&mut self, expected: usize) {
+    pub fn filter<M: MatcherTrait<A>>(&mut self, matcher: M) -> Instance<'_, Vec<&A>> {
+        if self.actual.is_missing() {
+            return self.projected("filter", Actual::Missing);
+        }
+        let actual = self.actual.iter().filter(|a| matcher.matcher_fn(a)).collect::<Vec<_>>();
+        self.projected("filter", Actual::Owned(Box::new(actual)))
+    }
+
+    pub fn flat_map<'b, E, I, T>(&mut self, mapper: T) -> Instance<'b, Vec<E>>
+    where
+        E: 'b,
+        I: IntoIterator<Item = E>,
+        T: FnMut(&A) -> I,
+    {
+        if self.actual.is_missing() {
+            return self.projected("flat_map", Actual::Missing);
+        }
+        let actual = self.actual.iter().flat_map(mapper).collect::<Vec<_>>();
+        self.projected("flat_map", Actual::Owned(Box::new(actual)))
+    }
+
+    pub fn first(&self) -> Instance<'_, A> {
+        self.element_instance("first", self.actual.first(), || {
+            format!("assertion failed: `(actual.first() exists)`\n     actual.len(): `{}`", self.actual.len())
+        })
+    }
+
+    pub fn last(&self) -> Instance<'_, A> {
+        self.element_instance("last", self.actual.last(), || {
+            format!("assertion failed: `(actual.last() exists)`\n     actual.len(): `{}`", self.actual.len())
+        })
+    }
+
+    pub fn element(&self, index: usize) -> Instance<'_, A> {
+        self.element_instance("element", self.actual.get(index), || {
+            format!("assertion failed: `(actual[{}] exists)`\n     actual.len(): `{}`", index, self.actual.len())
+        })
+    }
+
+    pub fn single(&self) -> Instance<'_, A> {
+        self.element_instance("single", self.actual.first().filter(|_| self.actual.len() == 1), || {
+            format!("assertion failed: `(actual.len() == 1)`\n     actual.len(): `{}`", self.actual.len())
+        })
+    }
+
+    fn element_instance<'s, L>(&'s self, accessor: &str, element: Option<&'s A>, log: L) -> Instance<'s, A>
+    where
+        L: FnOnce() -> String,
+    {
+        if self.actual.is_missing() {
+            return self.projected(accessor, Actual::Missing);
+        }
+        if self.instance_config.negation {
+            self.handle_execution(Execution {
+                ok: true,
+                log: String::new,
+                nlog: || format!("{} assertion cannot be negated", accessor),
+            });
+            return self.projected(accessor, Actual::Missing);
+        }
+        self.handle_execution(Execution {
+            ok: element.is_some(),
+            log,
+            nlog: String::new,
+        });
+        match element {
+            Some(e) => self.projected(accessor, Actual::Borrowed(e)),
+            None => self.projected(accessor, Actual::Missing),
+        }
+    }
+
+    pub fn has_len(&mut self, expected: usize) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
         let a_len = self.actual.as_ref().len();
         self.handle_execution(Execution {
             ok: a_len == expected,
-            log: format!(
-                r#"assertion failed: `(actual.len() == expectation)`
+            log: || {
+                format!(
+                    r#"assertion failed: `(actual.len() == expectation)`
      actual.len(): `{:?}`
 expectation: `{:?}`"#,
-                a_len, expected
-            ),
-            nlog: format!(
-                r#"assertion failed: `(actual.len() != expectation)`
+                    a_len, expected
+                )
+            },
+            nlog: || {
+                format!(
+                    r#"assertion failed: `(actual.len() != expectation)`
      actual.len(): `{:?}`
 expectation: `{:?}`"#,
-                a_len, expected
-            ),
+                    a_len, expected
+                )
+            },
         });
+        self.finish()
+    }
+
+    pub fn any<M>(&mut self, matcher: M) -> &mut Self
+    where
+        M: MatcherTrait<A>,
+    {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        if !self.instance_config.negation {
+            let found = self.actual.iter().any(|a| matcher.matcher_fn(a));
+            if !found {
+                self.handle_execution(Execution {
+                    ok: false,
+                    log: || "assertion failed: `(matcher failed for every item)`".to_string(),
+                    nlog: String::new,
+                });
+            }
+        } else {
+            let found = self.actual.iter().position(|a| matcher.matcher_fn(a));
+            if let Some(a) = found {
+                self.handle_execution(Execution {
+                    ok: true,
+                    log: String::new,
+                    nlog: || format!("assertion failed: `(matcher succeed for item position {:?})`", a),
+                });
+            }
+        }
+        self.finish()
+    }
+
+    pub fn each<M>(&mut self, matchers: &[M]) -> &mut Self
+    where
+        M: MatcherTrait<A>,
+    {
+        self.each_with(matchers, |_| None)
+    }
+
+    pub fn all<M>(&mut self, matcher: M) -> &mut Self
+    where
+        M: MatcherTrait<A>,
+    {
+        self.all_with(matcher, |_| None)
     }
 
-    pub fn each<M>(&mut self, matchers: &[M])
+    fn each_with<M, D>(&mut self, matchers: &[M], describe: D) -> &mut Self
     where
         M: MatcherTrait<A>,
+        D: Fn(&A) -> Option<String>,
     {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
         if self.instance_config.negation {
             self.handle_execution(Execution {
                 ok: true,
-                log: "".to_string(),
-                nlog: "each assertion cannot be negated".to_string(),
+                log: String::new,
+                nlog: || "each assertion cannot be negated".to_string(),
             });
-            return;
+            return self.finish();
         }
         if self.actual.len() != matchers.len() {
             let log = "matchers length is different from input length";
             self.handle_execution(Execution {
                 ok: false,
-                log: log.to_string(),
-                nlog: "".to_string(),
+                log: || log.to_string(),
+                nlog: String::new,
             });
-            return;
-        }
-        for pos in 0..self.actual.len() {
-            let a = self.actual.get(pos).unwrap();
-            if let Some(matcher) = matchers.get(pos) {
-                let ok = matcher.matcher_fn(a);
-                let log = matcher.log_fn(a);
-                self.handle_execution(Execution {
-                    ok,
-                    log: format!("{} - at position {}", log, pos),
-                    nlog: "".to_string(),
-                });
-            } else {
-                self.handle_execution(Execution {
-                    ok: false,
-                    log: format!("matcher not found as position {}", pos),
-                    nlog: "".to_string(),
-                });
-            }
+            return self.finish();
         }
+        let failed = self
+            .actual
+            .iter()
+            .zip(matchers)
+            .enumerate()
+            .filter(|(_, (a, matcher))| !matcher.matcher_fn(a))
+            .map(|(position, (a, matcher))| FailedRow {
+                position,
+                value: describe(a),
+                explanation: matcher.log_fn(a),
+            })
+            .collect::<Vec<_>>();
+        self.handle_item_failures(failed);
+        self.finish()
     }
 
-    pub fn all<M>(&mut self, matcher: M)
+    fn all_with<M, D>(&mut self, matcher: M, describe: D) -> &mut Self
     where
         M: MatcherTrait<A>,
+        D: Fn(&A) -> Option<String>,
     {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
         if !self.instance_config.negation {
-            for pos in 0..self.actual.len() {
-                let a = self.actual.get(pos).unwrap();
-                let ok = matcher.matcher_fn(a);
-                let log = matcher.log_fn(a);
-                self.handle_execution(Execution {
-                    ok,
-                    log: format!("{} - at position {}", log, pos),
-                    nlog: "".to_string(),
-                });
-            }
+            let failed = self
+                .actual
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| !matcher.matcher_fn(a))
+                .map(|(position, a)| FailedRow {
+                    position,
+                    value: describe(a),
+                    explanation: matcher.log_fn(a),
+                })
+                .collect::<Vec<_>>();
+            self.handle_item_failures(failed);
         } else {
             let found = self.actual.iter().any(|a| !matcher.matcher_fn(a));
             if !found {
                 self.handle_execution(Execution {
                     ok: true,
-                    log: "".to_string(),
-                    nlog: "assertion failed: `(matcher succeed for every item)`".to_string(),
+                    log: String::new,
+                    nlog: || "assertion failed: `(matcher succeed for every item)`".to_string(),
                 });
             }
         }
+        self.finish()
     }
 
-    pub fn any<M>(&mut self, matcher: M)
+    fn handle_item_failures(&self, failed: Vec<FailedRow>) {
+        match render_failure_table(self.actual.len(), &failed) {
+            Some(table) => self.handle_failure(|| table),
+            None => {
+                for line in render_failure_lines(&failed) {
+                    self.handle_failure(|| line);
+                }
+            }
+        }
+    }
+}
+
+impl<A> Instance<'_, Vec<A>>
+where
+    A: Debug + PartialOrd,
+{
+    pub fn is_sorted(&mut self) -> &mut Self {
+        if self.actual.is_missing() {
+            return self.finish();
+        }
+        let unsorted = self.actual.windows(2).position(|w| w[0] > w[1]);
+        self.handle_execution(Execution {
+            ok: unsorted.is_none(),
+            log: || match unsorted {
+                Some(pos) => format!(
+                    "assertion failed: `(actual is sorted)`\n     actual: `{:?}`\n   unsorted: `actual[{}] > actual[{}]`",
+                    self.actual,
+                    pos,
+                    pos + 1
+                ),
+                None => "".to_string(),
+            },
+            nlog: || format!("assertion failed: `(actual is not sorted)`\n     actual: `{:?}`", self.actual),
+        });
+        self.finish()
+    }
+}
+
+impl<A> Instance<'_, Vec<A>>
+where
+    A: Debug,
+{
+    pub fn each_as_table<M>(&mut self, matchers: &[M]) -> &mut Self
     where
         M: MatcherTrait<A>,
     {
-        if !self.instance_config.negation {
-            let found = self.actual.iter().any(|a| matcher.matcher_fn(a));
-            if !found {
-                self.handle_execution(Execution {
-                    ok: false,
-                    log: "assertion failed: `(matcher failed for every item)`".to_string(),
-                    nlog: "".to_string(),
-                });
-            }
-        } else {
-            let found = self.actual.iter().position(|a| matcher.matcher_fn(a));
-            if let Some(a) = found {
-                self.handle_execution(Execution {
-                    ok: true,
-                    log: "".to_string(),
-                    nlog: format!("assertion failed: `(matcher succeed for item position {:?})`", a),
-                });
-            }
+        self.each_with(matchers, |a| Some(format!("{:?}", a)))
+    }
+
+    pub fn all_as_table<M>(&mut self, matcher: M) -> &mut Self
+    where
+        M: MatcherTrait<A>,
+    {
+        self.all_with(matcher, |a| Some(format!("{:?}", a)))
+    }
+}
+
+impl<A> Instance<'_, Vec<A>>
+where
+    A: Clone,
+{
+    pub fn zip_with<'b, B>(&mut self, other: Vec<B>) -> Instance<'b, Vec<(A, B)>>
+    where
+        A: 'b,
+        B: 'b,
+    {
+        if self.actual.is_missing() {
+            return self.projected("zip_with", Actual::Missing);
+        }
+        self.handle_execution(Execution {
+            ok: self.actual.len() == other.len(),
+            log: || {
+                format!(
+                    "assertion failed: `(actual.len() == other.len())`\n     actual.len(): `{:?}`\n other.len(): `{:?}`",
+                    self.actual.len(),
+                    other.len()
+                )
+            },
+            nlog: || {
+                format!(
+                    "assertion failed: `(actual.len() != other.len())`\n     actual.len(): `{:?}`\n other.len(): `{:?}`",
+                    self.actual.len(),
+                    other.len()
+                )
+            },
+        });
+        self.finish();
+        let actual = self.actual.iter().cloned().zip(other).collect::<Vec<_>>();
+        self.projected("zip_with", Actual::Owned(Box::new(actual)))
+    }
+
+    pub fn group_by<'b, K, F>(&mut self, key: F) -> Instance<'b, BTreeMap<K, Vec<A>>>
+    where
+        A: 'b,
+        K: Ord + 'b,
+        F: Fn(&A) -> K,
+    {
+        if self.actual.is_missing() {
+            return self.projected("group_by", Actual::Missing);
+        }
+        let mut actual: BTreeMap<K, Vec<A>> = BTreeMap::new();
+        for a in self.actual.iter() {
+            actual.entry(key(a)).or_default().push(a.clone());
         }
+        self.projected("group_by", Actual::Owned(Box::new(actual)))
     }
 }
@@ -6,6 +6,7 @@ mod tests {
     use crate::assert_panic_ignored;
     use crate::fn_matcher;
     use std::panic;
+    use std::path::PathBuf;
 
     #[test]
     fn assert_should_pass_after_mapping() {
@@ -148,7 +149,7 @@ expectation: `3`"#
         let result = panic::catch_unwind(|| {
             let mut assert = assert::new_with_handler(&|fr: FailResult| {
                 Box::new(move || {
-                    assert_eq!(fr.log, "assertion failed: `(matcher \"&(|p| *p % 2 == 1)\" failed)` - at position 1");
+                    assert_eq!(fr.log, "assertion failed: `(matcher \"&|p| *p % 2 == 1\" failed)` - at position 1");
                 })
             });
             assert.that(vec![1, 2, 3]).each(&[fn_matcher!(&|p| *p > 0), fn_matcher!(&|p| *p % 2 == 1), fn_matcher!(&|p| *p == 3)]);
@@ -199,7 +200,7 @@ expectation: `3`"#
         let result = panic::catch_unwind(|| {
             let mut assert = assert::new_with_handler(&|fr: FailResult| {
                 Box::new(move || {
-                    assert_eq!(fr.log, "assertion failed: `(matcher \"&(|a| *a > 0)\" failed)` - at position 1");
+                    assert_eq!(fr.log, "assertion failed: `(matcher \"&|a| *a > 0\" failed)` - at position 1");
                 })
             });
             assert.that(vec![1, -2, 3]).all(fn_matcher!(&|a| *a > 0));
@@ -207,6 +208,15 @@ expectation: `3`"#
         assert_panic_ignored!(result)
     }
 
+    #[test]
+    fn assert_each_and_all_should_not_require_debug() {
+        struct NoDebug(i32);
+
+        let mut assert = assert::new();
+        assert.that(vec![NoDebug(1), NoDebug(2)]).all(fn_matcher!(&|a: &NoDebug| a.0 > 0));
+        assert.that(vec![NoDebug(1)]).each(&[fn_matcher!(&|a: &NoDebug| a.0 == 1)]);
+    }
+
     #[test]
     fn assert_all_should_fail_with_negation() {
         let result = panic::catch_unwind(|| {
@@ -260,4 +270,338 @@ expectation: `3`"#
         let mut assert = assert::new();
         assert.that(vec![-1, 2, -3]).any(fn_matcher!(&|a| *a > 0));
     }
+
+    #[test]
+    fn assert_contains_and_eq_each_should_pass_with_heterogeneous_expectations() {
+        let mut assert = assert::new();
+        assert.that(vec![String::from("a"), String::from("b")]).contains("b");
+        assert.that(vec![String::from("a"), String::from("b")]).not().contains("c");
+        assert.that(vec![String::from("a"), String::from("b")]).eq_each(&["a", "b"]);
+        assert.that(vec![vec![1u8], vec![2u8]]).eq_each(&[&[1u8][..], &[2u8][..]]);
+        assert.that(vec![PathBuf::from("a"), PathBuf::from("b")]).eq_each(&["a", "b"]);
+    }
+
+    #[test]
+    fn assert_should_pass_when_chained() {
+        let mut assert = assert::new();
+        assert.that(vec![1, 2, 3]).has_len(3).contains(2).is_sorted().all(fn_matcher!(&|a| *a > 0));
+        assert.that(vec![1, 2, 3]).not().contains(4).contains(1).not().has_len(2).has_len(3);
+    }
+
+    #[test]
+    fn assert_should_scope_negation_to_next_assertion_when_chained() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(expectation ∈ actual)`");
+                })
+            });
+            assert.that(vec![1, 2, 3]).not().contains(4).contains(5);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_is_sorted_should_fail() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual is sorted)`
+     actual: `[1, 3, 2]`
+   unsorted: `actual[1] > actual[2]`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 3, 2]).is_sorted();
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_is_sorted_should_fail_with_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual is not sorted)`
+     actual: `[1, 2, 2]`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2, 2]).not().is_sorted();
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_should_pass_on_borrowed_projections() {
+        let mut assert = assert::new();
+        let names = vec![String::from("a"), String::from("bc")];
+        assert.that_ref(&names).map_ref(|n| n.as_str()).eq_each(&["a", "bc"]).not().contains("d");
+        assert.that_ref(&names).map_ref(|n| n.as_bytes()).has_len(2);
+    }
+
+    #[test]
+    fn assert_element_accessors_should_pass() {
+        let mut assert = assert::new();
+        let v = vec![1, 2, 3];
+        assert.that_ref(&v).first().is_eq(1);
+        assert.that_ref(&v).last().is_eq(3).not().is_eq(1);
+        assert.that_ref(&v).element(1).is_eq(2);
+        assert.that(vec![4]).single().is_eq(4);
+        assert.that_ref(&v).filter(fn_matcher!(&|a| *a > 1)).has_len(2).eq_each(&[&2, &3]);
+        assert.that_ref(&v).filter(fn_matcher!(&|a| *a > 2)).single().is_eq(&3);
+    }
+
+    #[test]
+    fn assert_element_should_fail_when_missing() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual[3] exists)`
+     actual.len(): `3`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2, 3]).element(3).is_eq(4).not().is_eq(4);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_first_should_fail_when_empty() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(actual.first() exists)`\n     actual.len(): `0`");
+                })
+            });
+            assert.that(Vec::<u8>::new()).first().is_eq(1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_last_should_fail_when_empty() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "assertion failed: `(actual.last() exists)`\n     actual.len(): `0`");
+                })
+            });
+            assert.that(Vec::<u8>::new()).last().is_eq(1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_single_should_fail_with_several_elements() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual.len() == 1)`
+     actual.len(): `2`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2]).single().is_eq(1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_element_should_record_failures_on_element() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+     actual: `2`
+expectation: `3`
+ projection: `actual.element(..)`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2]).element(1).is_eq(3);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_element_should_prevent_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(fr.log, "first assertion cannot be negated");
+                })
+            });
+            assert.that(vec![1, 2]).not().first().is_eq(1);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_projections_should_pass() {
+        let mut assert = assert::new();
+        assert.that(vec![1, 2, 3, 4]).filter(fn_matcher!(&|a| a % 2 == 0)).eq_each(&[&2, &4]);
+        assert.that(vec![1, 2]).flat_map(|a| vec![*a; *a]).eq_each(&[1, 2, 2]);
+        assert.that(vec![1, 2]).zip_with(vec!["a", "b"]).is_eq(vec![(1, "a"), (2, "b")]);
+        assert.that(vec!["ab", "c", "de"]).group_by(|a| a.len()).contains_key(2).value(2).eq_each(&["ab", "de"]);
+    }
+
+    #[test]
+    fn assert_filter_should_borrow_elements() {
+        #[derive(Debug, PartialEq)]
+        struct Item(u8);
+
+        let mut assert = assert::new();
+        assert.that(vec![Item(1), Item(2)]).filter(fn_matcher!(&|i: &Item| i.0 > 1)).single().is_eq(&Item(2));
+    }
+
+    #[test]
+    fn assert_projections_should_fail_with_projection_chain() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(expectation ∈ actual)`
+ projection: `actual.filter(..).map(..)`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2, 3, 4]).filter(fn_matcher!(&|a| a % 2 == 0)).map(|a| *a * 10).contains(30);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_element_should_fail_with_projection_chain() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual == expectation)`
+     actual: `20`
+expectation: `30`
+ projection: `actual.map(..).first(..)`"#
+                    );
+                })
+            });
+            assert.that(vec![2, 3]).map(|a| a * 10).first().is_eq(30);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_projections_should_keep_negation() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(expectation ∉ actual)`
+ projection: `actual.flat_map(..)`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2]).not().flat_map(|a| vec![*a; *a]).contains(2);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_zip_with_should_fail_when_different_length() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual.len() == other.len())`
+     actual.len(): `3`
+ other.len(): `2`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2, 3]).zip_with(vec!["a", "b"]).has_len(2);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_zip_with_should_scope_negation_to_length_check() {
+        let mut assert = assert::new();
+        assert.that(vec![1, 2, 3]).not().zip_with(vec!["a", "b"]).is_eq(vec![(1, "a"), (2, "b")]);
+
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(actual.len() != other.len())`
+     actual.len(): `2`
+ other.len(): `2`"#
+                    );
+                })
+            });
+            assert.that(vec![1, 2]).not().zip_with(vec!["a", "b"]).has_len(2);
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[derive(Debug)]
+    struct Order {
+        id: u32,
+        price: i32,
+    }
+
+    #[test]
+    fn assert_all_as_table_should_fail_with_table_on_records() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(2 of 3 items failed)`
+position | id | price | explanation
+       0 | 1  | [-1]  | assertion failed: `(matcher "&|o: &Order| o.price > 0" failed)`
+       2 | 3  | [0]   | assertion failed: `(matcher "&|o: &Order| o.price > 0" failed)`"#
+                    );
+                })
+            });
+            assert
+                .that(vec![Order { id: 1, price: -1 }, Order { id: 2, price: 5 }, Order { id: 3, price: 0 }])
+                .all_as_table(fn_matcher!(&|o: &Order| o.price > 0));
+        });
+        assert_panic_ignored!(result)
+    }
+
+    #[test]
+    fn assert_each_as_table_should_fail_with_table_on_records() {
+        let result = panic::catch_unwind(|| {
+            let mut assert = assert::new_with_handler(&|fr: FailResult| {
+                Box::new(move || {
+                    assert_eq!(
+                        fr.log,
+                        r#"assertion failed: `(1 of 2 items failed)`
+position | id  | price | explanation
+       1 | [2] | 5     | assertion failed: `(matcher "&|o: &Order| o.id == 3" failed)`"#
+                    );
+                })
+            });
+            assert
+                .that(vec![Order { id: 1, price: -1 }, Order { id: 2, price: 5 }])
+                .each_as_table(&[fn_matcher!(&|o: &Order| o.id == 1), fn_matcher!(&|o: &Order| o.id == 3)]);
+        });
+        assert_panic_ignored!(result)
+    }
 }
@@ -1,153 +1,726 @@
 use crate::fs::fs_error::FsTestError;
-use crate::fs::fs_error::FsTestError::{NeedDir, NeedFile};
+use crate::fs::fs_error::FsTestError::{AlreadyExists, ContentUnavailable, DuplicateName, InvalidPath, NeedDir, NeedFile, NotFound};
+use crate::fs::platform;
+use crate::fs::read_options::{ReadFilter, ReadOptions};
+use crate::fs::write_options::{WriteOptions, WritePolicy, WriteReport};
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::fmt::{Display, Formatter};
 use std::fs;
 use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
 
+#[derive(Clone, Debug)]
 pub enum FileNode {
-    Dir { name: String, sub: Vec<FileNode> },
-    File { name: String, open_options: Option<OpenOptions>, content: Vec<u8> },
+    Dir {
+        name: OsString,
+        sub: Vec<FileNode>,
+        mode: Option<u32>,
+    },
+    File {
+        name: OsString,
+        open_options: Option<OpenOptions>,
+        content: Vec<u8>,
+        mode: Option<u32>,
+    },
+    // A file read above `ReadOptions::max_file_size`, only its size and `content_hash` are kept.
+    FileDigest {
+        name: OsString,
+        size: u64,
+        hash: u64,
+        mode: Option<u32>,
+    },
+    Symlink {
+        name: OsString,
+        target: PathBuf,
+    },
+    // `target` is relative to the root of the tree, like paths given to `get`.
+    HardLink {
+        name: OsString,
+        target: PathBuf,
+    },
+    Special {
+        name: OsString,
+        kind: SpecialKind,
+    },
 }
 
-impl PartialEq for FileNode {
-    fn eq(&self, other: &Self) -> bool {
-        return match self {
-            FileNode::Dir { sub, name } => match other {
-                FileNode::Dir { sub: o_sub, name: o_name } => {
-                    if name != o_name {
-                        return false;
-                    }
-                    if sub.len() != o_sub.len() {
-                        return false;
-                    }
-                    let mut sub_r: Vec<&FileNode> = sub.iter().collect();
-                    sub_r.sort_by_key(|f| f.get_name());
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpecialKind {
+    Fifo,
+    Socket,
+    BlockDevice { rdev: u64 },
+    CharDevice { rdev: u64 },
+}
 
-                    let mut o_sub_r: Vec<&FileNode> = o_sub.iter().collect();
-                    o_sub_r.sort_by_key(|f| f.get_name());
+impl Display for SpecialKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecialKind::Fifo => f.write_str("fifo"),
+            SpecialKind::Socket => f.write_str("socket"),
+            SpecialKind::BlockDevice { rdev } => write!(f, "block device {}", rdev),
+            SpecialKind::CharDevice { rdev } => write!(f, "char device {}", rdev),
+        }
+    }
+}
 
-                    for (i, e) in sub_r.iter().enumerate() {
-                        if &o_sub_r[i] != e {
-                            return false;
-                        }
-                    }
-                    true
-                }
-                _ => false,
-            },
-            FileNode::File { content, name, .. } => match other {
-                FileNode::File { content: o_content, name: o_name, .. } => name == o_name && content == o_content,
-                _ => false,
-            },
-        };
+impl PartialEq for FileNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_with(other, false)
     }
 }
 
 impl FileNode {
-    pub fn new_dir(name: &str) -> Self {
+    pub fn new_dir<N: AsRef<OsStr>>(name: N) -> Self {
         FileNode::Dir {
-            name: String::from(name),
+            name: name.as_ref().to_os_string(),
             sub: Vec::new(),
+            mode: None,
         }
     }
 
-    pub fn new_file(name: &str, content: Vec<u8>) -> Self {
+    pub fn new_file<N: AsRef<OsStr>>(name: N, content: Vec<u8>) -> Self {
         FileNode::File {
-            name: String::from(name),
+            name: name.as_ref().to_os_string(),
             open_options: None,
             content,
+            mode: None,
+        }
+    }
+
+    pub fn new_file_with_mode<N: AsRef<OsStr>>(name: N, content: Vec<u8>, mode: u32) -> Self {
+        FileNode::File {
+            name: name.as_ref().to_os_string(),
+            open_options: None,
+            content,
+            mode: Some(mode),
+        }
+    }
+
+    pub fn new_symlink<N: AsRef<OsStr>, P: AsRef<Path>>(name: N, target: P) -> Self {
+        FileNode::Symlink {
+            name: name.as_ref().to_os_string(),
+            target: target.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn new_hard_link<N: AsRef<OsStr>, P: AsRef<Path>>(name: N, target: P) -> Self {
+        FileNode::HardLink {
+            name: name.as_ref().to_os_string(),
+            target: target.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn new_special<N: AsRef<OsStr>>(name: N, kind: SpecialKind) -> Self {
+        FileNode::Special {
+            name: name.as_ref().to_os_string(),
+            kind,
         }
     }
 
     pub fn new_from_path(pb: &Path) -> Result<Self, FsTestError> {
-        let mut f: FileNode;
-        if pb.is_file() {
-            f = FileNode::new_file("", vec![]);
-        } else {
-            f = FileNode::new_dir("");
+        FileNode::new_from_path_with(pb, &ReadOptions::default())
+    }
+
+    pub fn new_from_path_with(pb: &Path, options: &ReadOptions) -> Result<Self, FsTestError> {
+        let mut ancestors = vec![];
+        let mut node = read_node(pb, Path::new(""), 0, options, &ReadFilter::new(options)?, &mut ancestors)?.unwrap_or_else(|| FileNode::new_dir(get_file_name(pb)));
+        node.link_hard_links(pb)?;
+        Ok(node)
+    }
+
+    // 64-bit FNV-1a, stable across runs and platforms.
+    pub fn content_hash(content: &[u8]) -> u64 {
+        fnv1a(FNV_OFFSET, content)
+    }
+
+    pub fn get_mode(&self) -> Option<u32> {
+        match self {
+            FileNode::Dir { mode, .. } | FileNode::File { mode, .. } | FileNode::FileDigest { mode, .. } => *mode,
+            FileNode::Symlink { .. } | FileNode::HardLink { .. } | FileNode::Special { .. } => None,
         }
-        f.feed_from_path(pb)?;
-        Ok(f)
     }
 
-    pub fn write_to_path(&self, root_dir: &Path) -> Result<(), FsTestError> {
+    // Modes are only compared when both nodes define one.
+    pub fn eq_with_modes(&self, other: &FileNode) -> bool {
+        self.eq_with(other, true)
+    }
+
+    fn eq_with(&self, other: &FileNode, modes: bool) -> bool {
+        if self.get_name() != other.get_name() {
+            return false;
+        }
+        if let (true, Some(mode), Some(o_mode)) = (modes, self.get_mode(), other.get_mode()) {
+            if mode != o_mode {
+                return false;
+            }
+        }
+        match (self, other) {
+            (FileNode::Dir { sub, .. }, FileNode::Dir { sub: o_sub, .. }) => {
+                if sub.len() != o_sub.len() {
+                    return false;
+                }
+                if is_sorted(sub) && is_sorted(o_sub) {
+                    return sub.iter().zip(o_sub.iter()).all(|(e, o)| e.eq_with(o, modes));
+                }
+                sorted(sub).iter().zip(sorted(o_sub).iter()).all(|(e, o)| e.eq_with(o, modes))
+            }
+            (FileNode::File { .. } | FileNode::FileDigest { .. }, FileNode::File { .. } | FileNode::FileDigest { .. }) => self.same_content(other),
+            (FileNode::Symlink { target, .. }, FileNode::Symlink { target: o_target, .. }) => target == o_target,
+            (FileNode::HardLink { target, .. }, FileNode::HardLink { target: o_target, .. }) => target == o_target,
+            (FileNode::Special { kind, .. }, FileNode::Special { kind: o_kind, .. }) => kind == o_kind,
+            _ => false,
+        }
+    }
+
+    // A file and a digest are compared by size and hash, since the digest no longer holds the content.
+    pub(crate) fn same_content(&self, other: &FileNode) -> bool {
+        match (self, other) {
+            (FileNode::File { content, .. }, FileNode::File { content: o_content, .. }) => content == o_content,
+            (FileNode::File { content, .. }, FileNode::FileDigest { size, hash, .. }) | (FileNode::FileDigest { size, hash, .. }, FileNode::File { content, .. }) => {
+                content.len() as u64 == *size && FileNode::content_hash(content) == *hash
+            }
+            (FileNode::FileDigest { size, hash, .. }, FileNode::FileDigest { size: o_size, hash: o_hash, .. }) => size == o_size && hash == o_hash,
+            _ => false,
+        }
+    }
+
+    pub fn write_to_path(&self, root_dir: &Path) -> Result<WriteReport, FsTestError> {
+        self.write_to_path_with(root_dir, &WriteOptions::default())
+    }
+
+    pub fn write_to_path_with(&self, root_dir: &Path, options: &WriteOptions) -> Result<WriteReport, FsTestError> {
         if !root_dir.is_dir() {
             return Err(NeedDir);
         }
+        // A dry run first so that nothing is written when an entry already exists.
+        if options.policy == WritePolicy::FailIfExists && !options.dry_run {
+            self.write_to_path_with(root_dir, &options.clone().dry_run())?;
+        }
+        let mut report = WriteReport::default();
+        let mut hard_links = vec![];
+        self.write_node(root_dir, Path::new(""), options, &mut report, &mut hard_links)?;
+        // Hard links are written last so that their targets exist whatever the order of the children.
+        let top = root_dir.join(self.get_name());
+        for (path, rel_path, target) in hard_links {
+            let target = top.join(target);
+            let same = platform::same_file(&path, &target);
+            replace_entry(&path, rel_path, same, options, &mut report, || fs::hard_link(&target, &path))?;
+        }
+        Ok(report)
+    }
+
+    // In dry run mode nothing is touched on disk, the report is computed against the current directory contents.
+    fn write_node(&self, root_dir: &Path, rel_dir: &Path, options: &WriteOptions, report: &mut WriteReport, hard_links: &mut Vec<(PathBuf, PathBuf, PathBuf)>) -> Result<(), FsTestError> {
+        let sync = options.policy == WritePolicy::Sync;
         match self {
-            FileNode::Dir { name, sub } => {
+            FileNode::Dir { name, sub, mode } => {
+                if let Some((_, duplicate)) = sub.iter().enumerate().find(|(i, n)| sub[..*i].iter().any(|o| o.get_name() == n.get_name())) {
+                    return Err(DuplicateName(duplicate.get_name().to_os_string()));
+                }
                 let dir_path = root_dir.join(name);
-                if !&dir_path.exists() {
-                    fs::create_dir(&dir_path)?;
+                let rel_path = rel_dir.join(name);
+                // Links and other non-directory entries are replaced rather than written through.
+                let is_dir = match existing_metadata(&dir_path, &rel_path, options, report) {
+                    Some(m) if !m.is_dir() => match options.policy {
+                        _ if m.is_file() && !sync => return Err(NeedDir),
+                        WritePolicy::FailIfExists => return Err(AlreadyExists(dir_path)),
+                        WritePolicy::SkipExisting => {
+                            report.unchanged.push(rel_path);
+                            return Ok(());
+                        }
+                        _ => {
+                            if !options.dry_run {
+                                remove_entry(&dir_path)?;
+                            }
+                            report.deleted.push(rel_path.clone());
+                            false
+                        }
+                    },
+                    Some(_) => true,
+                    None => false,
+                };
+                if is_dir {
+                    report.unchanged.push(rel_path.clone());
+                } else {
+                    if !options.dry_run {
+                        fs::create_dir(&dir_path)?;
+                    }
+                    report.created.push(rel_path.clone());
                 }
                 for sub_node in sub {
-                    sub_node.write_to_path(&dir_path)?;
+                    sub_node.write_node(&dir_path, &rel_path, options, report, hard_links)?;
+                }
+                if sync && is_dir {
+                    let mut extra = vec![];
+                    for entry in fs::read_dir(&dir_path)? {
+                        let entry = entry?;
+                        if !sub.iter().any(|n| entry.file_name().as_os_str() == n.get_name()) {
+                            extra.push(entry);
+                        }
+                    }
+                    extra.sort_by_key(|e| e.file_name());
+                    for entry in extra {
+                        if !options.dry_run {
+                            remove_entry(&entry.path())?;
+                        }
+                        report.deleted.push(rel_path.join(entry.file_name()));
+                    }
+                }
+                if let (false, Some(mode)) = (options.dry_run, mode) {
+                    platform::set_mode(&dir_path, *mode)?;
                 }
                 Ok(())
             }
-            FileNode::File { name, content, open_options } => {
+            FileNode::File { name, content, open_options, mode } => {
                 let path = root_dir.join(name);
-                let f = if !path.exists() {
-                    File::create(path)?
-                } else if let Some(oo) = open_options {
-                    oo.open(path)?
+                let rel_path = rel_dir.join(name);
+                // Links and other non-regular entries are replaced rather than written through.
+                let exists = match existing_metadata(&path, &rel_path, options, report) {
+                    Some(m) if !m.is_file() => match options.policy {
+                        WritePolicy::FailIfExists => return Err(AlreadyExists(path)),
+                        WritePolicy::SkipExisting => {
+                            report.unchanged.push(rel_path);
+                            return Ok(());
+                        }
+                        WritePolicy::Truncate | WritePolicy::Append if m.is_dir() => return Err(NeedFile),
+                        _ => {
+                            if !options.dry_run {
+                                remove_entry(&path)?;
+                            }
+                            report.deleted.push(rel_path.clone());
+                            false
+                        }
+                    },
+                    Some(_) => true,
+                    None => false,
+                };
+                if !exists {
+                    if !options.dry_run {
+                        let mut oo = open_options.clone().unwrap_or_else(fs::OpenOptions::new);
+                        let f = oo.write(true).create_new(true).open(&path)?;
+                        write_content(&f, content)?;
+                        if let Some(mode) = mode {
+                            platform::set_mode(&path, *mode)?;
+                        }
+                    }
+                    report.created.push(rel_path);
+                    return Ok(());
+                }
+                match options.policy {
+                    WritePolicy::FailIfExists => return Err(AlreadyExists(path)),
+                    WritePolicy::SkipExisting => {
+                        report.unchanged.push(rel_path);
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+                let previous = fs::read(&path)?;
+                let append = options.policy == WritePolicy::Append;
+                let next = if !options.dry_run {
+                    // `custom_flags` would override the flags of user provided options, so those rely on the check above.
+                    let f = match open_options {
+                        Some(oo) if options.policy == WritePolicy::Truncate => oo.open(&path)?,
+                        Some(oo) if append => oo.clone().truncate(false).append(true).open(&path)?,
+                        Some(oo) => oo.clone().append(false).write(true).truncate(true).open(&path)?,
+                        None if append => platform::no_follow(fs::OpenOptions::new().append(true)).open(&path)?,
+                        None => platform::no_follow(fs::OpenOptions::new().write(true).truncate(true)).open(&path)?,
+                    };
+                    write_content(&f, content)?;
+                    if let Some(mode) = mode {
+                        platform::set_mode(&path, *mode)?;
+                    }
+                    fs::read(&path)?
+                } else if append {
+                    [previous.as_slice(), content].concat()
                 } else {
-                    fs::OpenOptions::new().write(true).open(path)?
+                    content.clone()
                 };
-                let mut f = BufWriter::new(&f);
-                f.write_all(&content)?;
-                f.flush()?;
+                if next == previous {
+                    report.unchanged.push(rel_path);
+                } else {
+                    report.modified.push(rel_path);
+                }
                 Ok(())
             }
+            FileNode::FileDigest { name, .. } => Err(ContentUnavailable(rel_dir.join(name))),
+            FileNode::Symlink { name, target } => {
+                let path = root_dir.join(name);
+                let same = fs::read_link(&path).is_ok_and(|t| &t == target);
+                replace_entry(&path, rel_dir.join(name), same, options, report, || platform::symlink(target, &path))
+            }
+            FileNode::HardLink { name, target } => {
+                hard_links.push((root_dir.join(name), rel_dir.join(name), target.clone()));
+                Ok(())
+            }
+            FileNode::Special { name, kind } => {
+                let path = root_dir.join(name);
+                let same = fs::symlink_metadata(&path).is_ok_and(|m| platform::special_kind(&m) == Some(*kind));
+                replace_entry(&path, rel_dir.join(name), same, options, report, || platform::create_special(&path, *kind))
+            }
         }
     }
 
     pub fn feed_from_path(&mut self, pb: &Path) -> Result<(), FsTestError> {
         match self {
-            FileNode::Dir { ref mut name, sub } => {
+            FileNode::Dir { ref mut name, sub, ref mut mode } => {
                 if !pb.is_dir() {
                     return Err(NeedDir);
                 }
-                *name = get_file_name(pb);
-                for entry in fs::read_dir(pb)? {
-                    let sub_path = entry?.path();
-                    let mut s: FileNode;
-                    if sub_path.is_file() {
-                        s = FileNode::File {
-                            name: "".to_string(),
-                            content: vec![],
-                            open_options: None,
-                        };
-                    } else {
-                        s = FileNode::Dir { name: "".to_string(), sub: vec![] };
-                    }
-                    s.feed_from_path(&sub_path)?;
-                    sub.push(s);
+                if let FileNode::Dir { name: n, sub: s, mode: m } = FileNode::new_from_path(pb)? {
+                    *name = n;
+                    *mode = m;
+                    sub.extend(s);
+                    sub.sort_by(|a, b| a.get_name().cmp(b.get_name()));
                 }
                 Ok(())
             }
-            FileNode::File { ref mut name, ref mut content, .. } => {
+            FileNode::File {
+                ref mut name,
+                ref mut content,
+                ref mut mode,
+                ..
+            } => {
                 if !pb.is_file() {
                     return Err(NeedFile);
                 }
                 *name = get_file_name(pb);
                 *content = fs::read(pb)?;
+                *mode = platform::mode_of(&fs::metadata(pb)?);
                 Ok(())
             }
+            FileNode::FileDigest { .. } | FileNode::Symlink { .. } | FileNode::HardLink { .. } | FileNode::Special { .. } => {
+                *self = FileNode::new_from_path(pb)?;
+                Ok(())
+            }
+        }
+    }
+
+    // Every file sharing an inode with a file met earlier in walk order becomes a hard link to it.
+    fn link_hard_links(&mut self, pb: &Path) -> Result<(), FsTestError> {
+        let mut first_paths: HashMap<(u64, u64), PathBuf> = HashMap::new();
+        let files = self.walk().filter(|(_, n)| matches!(n, FileNode::File { .. })).map(|(p, _)| p).collect::<Vec<_>>();
+        for path in files {
+            let inode = match platform::linked_inode(&fs::symlink_metadata(pb.join(&path))?) {
+                Some(inode) => inode,
+                None => continue,
+            };
+            match first_paths.get(&inode) {
+                Some(target) => {
+                    if let Some(node) = self.get_mut(&path) {
+                        *node = FileNode::new_hard_link(node.get_name(), target);
+                    }
+                }
+                None => {
+                    first_paths.insert(inode, path);
+                }
+            }
         }
+        Ok(())
     }
 
-    pub fn get_name(&self) -> &str {
+    pub fn get_name(&self) -> &OsStr {
         match self {
-            FileNode::Dir { name, .. } => &name,
-            FileNode::File { name, .. } => &name,
+            FileNode::Dir { name, .. } => name,
+            FileNode::File { name, .. } => name,
+            FileNode::FileDigest { name, .. } => name,
+            FileNode::Symlink { name, .. } => name,
+            FileNode::HardLink { name, .. } => name,
+            FileNode::Special { name, .. } => name,
+        }
+    }
+
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&FileNode> {
+        path_names(path.as_ref()).ok()?.into_iter().try_fold(self, |node, name| match node {
+            FileNode::Dir { sub, .. } => sub.iter().find(|n| n.get_name() == name),
+            _ => None,
+        })
+    }
+
+    pub fn get_mut<P: AsRef<Path>>(&mut self, path: P) -> Option<&mut FileNode> {
+        path_names(path.as_ref()).ok()?.into_iter().try_fold(self, |node, name| match node {
+            FileNode::Dir { sub, .. } => sub.iter_mut().find(|n| n.get_name() == name),
+            _ => None,
+        })
+    }
+
+    pub fn insert_at<P: AsRef<Path>>(&mut self, path: P, node: FileNode) -> Result<(), FsTestError> {
+        let mut dir = self;
+        for name in path_names(path.as_ref())? {
+            dir = match dir {
+                FileNode::Dir { sub, .. } => {
+                    let i = match sub.iter().position(|n| n.get_name() == name) {
+                        Some(i) => i,
+                        None => insert_sorted(sub, FileNode::new_dir(name)),
+                    };
+                    &mut sub[i]
+                }
+                _ => return Err(NeedDir),
+            };
+        }
+        match dir {
+            FileNode::Dir { sub, .. } => {
+                if sub.iter().any(|n| n.get_name() == node.get_name()) {
+                    return Err(DuplicateName(node.get_name().to_os_string()));
+                }
+                insert_sorted(sub, node);
+                Ok(())
+            }
+            _ => Err(NeedDir),
         }
     }
+
+    pub fn remove<P: AsRef<Path>>(&mut self, path: P) -> Option<FileNode> {
+        let mut names = path_names(path.as_ref()).ok()?;
+        let name = names.pop()?;
+        match self.get_mut(names.iter().collect::<PathBuf>())? {
+            FileNode::Dir { sub, .. } => {
+                let i = sub.iter().position(|n| n.get_name() == name)?;
+                Some(sub.remove(i))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn rename<P: AsRef<Path>, N: AsRef<OsStr>>(&mut self, path: P, new_name: N) -> Result<(), FsTestError> {
+        let path = path.as_ref();
+        let new_name = new_name.as_ref();
+        let mut names = path_names(path)?;
+        if let Some(name) = names.pop() {
+            if let Some(FileNode::Dir { sub, .. }) = self.get(names.iter().collect::<PathBuf>()) {
+                if name != new_name && sub.iter().any(|n| n.get_name() == new_name) {
+                    return Err(DuplicateName(new_name.to_os_string()));
+                }
+            }
+        }
+        match self.get_mut(path).ok_or_else(|| NotFound(path.to_path_buf()))? {
+            FileNode::Dir { name, .. }
+            | FileNode::File { name, .. }
+            | FileNode::FileDigest { name, .. }
+            | FileNode::Symlink { name, .. }
+            | FileNode::HardLink { name, .. }
+            | FileNode::Special { name, .. } => *name = new_name.to_os_string(),
+        }
+        if let Some(FileNode::Dir { sub, .. }) = path.parent().and_then(|parent| self.get_mut(parent)) {
+            sub.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+        }
+        Ok(())
+    }
+
+    pub fn walk(&self) -> Walk<'_> {
+        let mut walk = Walk { stack: vec![] };
+        walk.push_children(Path::new(""), self);
+        walk
+    }
+}
+
+pub struct Walk<'a> {
+    stack: Vec<(PathBuf, &'a FileNode)>,
+}
+
+impl<'a> Walk<'a> {
+    fn push_children(&mut self, path: &Path, node: &'a FileNode) {
+        if let FileNode::Dir { sub, .. } = node {
+            self.stack.extend(sorted(sub).into_iter().rev().map(|n| (path.join(n.get_name()), n)));
+        }
+    }
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = (PathBuf, &'a FileNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.stack.pop()?;
+        self.push_children(&path, node);
+        Some((path, node))
+    }
+}
+
+// Children read from disk or inserted through `insert_at` are kept sorted by name, so most
+// trees take the linear paths below; hand-built `sub` vectors are sorted on demand.
+fn is_sorted(sub: &[FileNode]) -> bool {
+    sub.is_sorted_by(|a, b| a.get_name() <= b.get_name())
+}
+
+fn sorted(sub: &[FileNode]) -> Vec<&FileNode> {
+    let mut sorted = sub.iter().collect::<Vec<_>>();
+    if !is_sorted(sub) {
+        sorted.sort_by_key(|n| n.get_name());
+    }
+    sorted
+}
+
+fn insert_sorted(sub: &mut Vec<FileNode>, node: FileNode) -> usize {
+    let i = sub.partition_point(|n| n.get_name() < node.get_name());
+    sub.insert(i, node);
+    i
+}
+
+fn path_names(path: &Path) -> Result<Vec<&OsStr>, FsTestError> {
+    let mut names = vec![];
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(name) => names.push(name),
+            _ => return Err(InvalidPath(path.to_path_buf())),
+        }
+    }
+    Ok(names)
+}
+
+// Returns `None` for entries filtered out by `options`; the root itself is never filtered.
+fn read_node(pb: &Path, rel_path: &Path, depth: usize, options: &ReadOptions, filter: &ReadFilter, ancestors: &mut Vec<PathBuf>) -> Result<Option<FileNode>, FsTestError> {
+    let mut metadata = fs::symlink_metadata(pb)?;
+    let name = get_file_name(pb);
+    let is_root = rel_path.as_os_str().is_empty();
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(pb)?;
+        // The root is always followed, dangling links and links to a directory being read are kept as links.
+        let followed = match fs::metadata(pb) {
+            Ok(followed) if (is_root || options.follow_symlinks) && !(followed.is_dir() && ancestors.contains(&fs::canonicalize(pb)?)) => Some(followed),
+            _ => None,
+        };
+        match followed {
+            Some(followed) => metadata = followed,
+            None if is_root || filter.accepts(rel_path, false) => return Ok(Some(FileNode::Symlink { name, target })),
+            None => return Ok(None),
+        }
+    }
+    if !is_root && !filter.accepts(rel_path, metadata.is_dir()) {
+        return Ok(None);
+    }
+    let mode = platform::mode_of(&metadata);
+    if let Some(kind) = platform::special_kind(&metadata) {
+        Ok(Some(FileNode::Special { name, kind }))
+    } else if metadata.is_dir() {
+        let mut sub = vec![];
+        if options.max_depth.is_none_or(|max_depth| depth < max_depth) {
+            let filter = filter.with_ignore_files(options, pb, rel_path)?;
+            ancestors.push(fs::canonicalize(pb)?);
+            for entry in fs::read_dir(pb)? {
+                let entry = entry?;
+                if let Some(node) = read_node(&entry.path(), &rel_path.join(entry.file_name()), depth + 1, options, &filter, ancestors)? {
+                    sub.push(node);
+                }
+            }
+            ancestors.pop();
+            sub.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+            // Directories left empty by include patterns are dropped unless they match one themselves.
+            if sub.is_empty() && !is_root && !options.include.is_empty() && !filter.includes(rel_path, true) {
+                return Ok(None);
+            }
+        }
+        Ok(Some(FileNode::Dir { name, sub, mode }))
+    } else if options.max_file_size.is_some_and(|max_size| metadata.len() > max_size) {
+        Ok(Some(FileNode::FileDigest {
+            name,
+            size: metadata.len(),
+            hash: hash_file(pb)?,
+            mode,
+        }))
+    } else {
+        Ok(Some(FileNode::File {
+            name,
+            open_options: None,
+            content: fs::read(pb)?,
+            mode,
+        }))
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn hash_file(pb: &Path) -> Result<u64, FsTestError> {
+    let mut f = File::open(pb)?;
+    let mut buffer = [0u8; 8192];
+    let mut hash = FNV_OFFSET;
+    loop {
+        let read = f.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hash);
+        }
+        hash = fnv1a(hash, &buffer[..read]);
+    }
+}
+
+// Links are recreated rather than written through, an existing entry at `path` is replaced as a whole.
+fn replace_entry<F>(path: &Path, rel_path: PathBuf, same: bool, options: &WriteOptions, report: &mut WriteReport, create: F) -> Result<(), FsTestError>
+where
+    F: FnOnce() -> std::io::Result<()>,
+{
+    let existing = match existing_metadata(path, &rel_path, options, report) {
+        Some(existing) => existing,
+        None => {
+            if !options.dry_run {
+                create()?;
+            }
+            report.created.push(rel_path);
+            return Ok(());
+        }
+    };
+    if same {
+        report.unchanged.push(rel_path);
+        return Ok(());
+    }
+    match options.policy {
+        WritePolicy::FailIfExists => return Err(AlreadyExists(path.to_path_buf())),
+        WritePolicy::SkipExisting => report.unchanged.push(rel_path),
+        WritePolicy::Sync => {
+            if !options.dry_run {
+                remove_entry(path)?;
+                create()?;
+            }
+            report.modified.push(rel_path);
+        }
+        WritePolicy::Truncate | WritePolicy::Append => {
+            if existing.is_dir() {
+                return Err(NeedFile);
+            }
+            if !options.dry_run {
+                fs::remove_file(path)?;
+                create()?;
+            }
+            report.modified.push(rel_path);
+        }
+    }
+    Ok(())
+}
+
+// In dry run, entries below a replaced entry are still on disk but would be gone after a real run.
+fn existing_metadata(path: &Path, rel_path: &Path, options: &WriteOptions, report: &WriteReport) -> Option<fs::Metadata> {
+    if options.dry_run && report.deleted.iter().any(|d| rel_path != d && rel_path.starts_with(d)) {
+        return None;
+    }
+    fs::symlink_metadata(path).ok()
+}
+
+fn remove_entry(path: &Path) -> Result<(), FsTestError> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
 }
 
-fn get_file_name(pb: &Path) -> String {
-    pb.file_name().unwrap_or_default().to_str().unwrap_or_default().to_string()
+fn write_content(f: &File, content: &[u8]) -> Result<(), FsTestError> {
+    let mut f = BufWriter::new(f);
+    f.write_all(content)?;
+    f.flush()?;
+    Ok(())
+}
+
+fn get_file_name(pb: &Path) -> OsString {
+    pb.file_name().unwrap_or_default().to_os_string()
 }
 
 #[cfg(test)]
@@ -167,40 +740,49 @@ mod tests {
 
         fn make_folder() -> FileNode {
             FileNode::Dir {
-                name: "root".to_string(),
+                name: "root".into(),
+                mode: None,
                 sub: vec![
                     FileNode::File {
-                        name: "fr1".to_string(),
+                        name: "fr1".into(),
+                        mode: None,
                         content: Vec::new(),
                         open_options: None,
                     },
                     FileNode::File {
-                        name: "fr1".to_string(),
+                        name: "fr1".into(),
+                        mode: None,
                         content: "file content r2".as_bytes().to_vec(),
                         open_options: None,
                     },
                     FileNode::Dir {
-                        name: "d1".to_string(),
+                        name: "d1".into(),
+                        mode: None,
                         sub: vec![
                             FileNode::File {
-                                name: "f1".to_string(),
+                                name: "f1".into(),
+                                mode: None,
                                 content: "file content 1".as_bytes().to_vec(),
                                 open_options: None,
                             },
                             FileNode::File {
-                                name: "f2".to_string(),
+                                name: "f2".into(),
+                                mode: None,
                                 content: "file content 2".as_bytes().to_vec(),
                                 open_options: None,
                             },
                             FileNode::File {
-                                name: "f3".to_string(),
+                                name: "f3".into(),
+                                mode: None,
                                 content: "file content 3".as_bytes().to_vec(),
                                 open_options: None,
                             },
                             FileNode::Dir {
-                                name: "d11".to_string(),
+                                name: "d11".into(),
+                                mode: None,
                                 sub: vec![FileNode::File {
-                                    name: "f11".to_string(),
+                                    name: "f11".into(),
+                                    mode: None,
                                     content: "file content 11".as_bytes().to_vec(),
                                     open_options: None,
                                 }],
@@ -208,7 +790,8 @@ mod tests {
                         ],
                     },
                     FileNode::Dir {
-                        name: "empty_dir".to_string(),
+                        name: "empty_dir".into(),
+                        mode: None,
                         sub: vec![],
                     },
                 ],
@@ -226,7 +809,8 @@ mod tests {
 
         let file_name = Uuid::new_v4().to_string();
         let file_node = FileNode::File {
-            name: file_name,
+            name: file_name.into(),
+            mode: None,
             content: "file content".as_bytes().to_vec(),
             open_options: None,
         };
@@ -240,7 +824,11 @@ mod tests {
         let tmp_path = temp_dir();
         let folder_name = Uuid::new_v4().to_string();
         let tmp_folder_path = tmp_path.join(&folder_name);
-        let file_node = FileNode::Dir { name: folder_name, sub: vec![] };
+        let file_node = FileNode::Dir {
+            name: folder_name.into(),
+            sub: vec![],
+            mode: None,
+        };
 
         file_node.write_to_path(&tmp_path).unwrap();
         file_node.write_to_path(&tmp_path).unwrap();
@@ -254,40 +842,49 @@ mod tests {
         let folder_root_name = Uuid::new_v4().to_string();
 
         let root_dir = FileNode::Dir {
-            name: folder_root_name.clone(),
+            name: folder_root_name.clone().into(),
+            mode: None,
             sub: vec![
                 FileNode::File {
-                    name: "fr1".to_string(),
+                    name: "fr1".into(),
+                    mode: None,
                     content: Vec::new(),
                     open_options: None,
                 },
                 FileNode::File {
-                    name: "fr2".to_string(),
+                    name: "fr2".into(),
+                    mode: None,
                     content: "file content fr2".as_bytes().to_vec(),
                     open_options: None,
                 },
                 FileNode::Dir {
-                    name: "d1".to_string(),
+                    name: "d1".into(),
+                    mode: None,
                     sub: vec![
                         FileNode::File {
-                            name: "f1".to_string(),
+                            name: "f1".into(),
+                            mode: None,
                             content: "file content 1".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f2".to_string(),
+                            name: "f2".into(),
+                            mode: None,
                             content: "file content 2".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f3".to_string(),
+                            name: "f3".into(),
+                            mode: None,
                             content: "file content 3".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::Dir {
-                            name: "d11".to_string(),
+                            name: "d11".into(),
+                            mode: None,
                             sub: vec![FileNode::File {
-                                name: "f11".to_string(),
+                                name: "f11".into(),
+                                mode: None,
                                 content: "file content 11".as_bytes().to_vec(),
                                 open_options: None,
                             }],
@@ -295,7 +892,8 @@ mod tests {
                     ],
                 },
                 FileNode::Dir {
-                    name: "empty_dir".to_string(),
+                    name: "empty_dir".into(),
+                    mode: None,
                     sub: vec![],
                 },
             ],
@@ -304,11 +902,14 @@ mod tests {
         root_dir.write_to_path(&tmp_path).unwrap();
 
         FileNode::Dir {
-            name: folder_root_name.clone(),
+            name: folder_root_name.clone().into(),
+            mode: None,
             sub: vec![FileNode::Dir {
-                name: "d1".to_string(),
+                name: "d1".into(),
+                mode: None,
                 sub: vec![FileNode::File {
-                    name: "f4".to_string(),
+                    name: "f4".into(),
+                    mode: None,
                     content: "file content 4".as_bytes().to_vec(),
                     open_options: None,
                 }],
@@ -355,7 +956,11 @@ mod tests {
         let tmp_path = temp_dir();
         let folder_name = Uuid::new_v4().to_string();
         let tmp_folder_path = tmp_path.join(&folder_name);
-        let file_node = FileNode::Dir { name: folder_name, sub: vec![] };
+        let file_node = FileNode::Dir {
+            name: folder_name.into(),
+            sub: vec![],
+            mode: None,
+        };
 
         file_node.write_to_path(&tmp_path).unwrap();
 
@@ -369,7 +974,8 @@ mod tests {
         let tmp_path = temp_dir();
         let file_name = Uuid::new_v4().to_string();
         let file_node = FileNode::File {
-            name: file_name.clone(),
+            name: file_name.clone().into(),
+            mode: None,
             content: "file content".as_bytes().to_vec(),
             open_options: None,
         };
@@ -380,7 +986,8 @@ mod tests {
         assert_eq!(fs::read_to_string(tmp_path.join(&file_name)).unwrap(), "file content");
 
         let file_node = FileNode::File {
-            name: file_name.clone(),
+            name: file_name.clone().into(),
+            mode: None,
             content: "file content updated".as_bytes().to_vec(),
             open_options: None,
         };
@@ -398,7 +1005,8 @@ mod tests {
         let tmp_path = temp_dir();
         let file_name = Uuid::new_v4().to_string();
         let file_node = FileNode::File {
-            name: file_name.clone(),
+            name: file_name.clone().into(),
+            mode: None,
             content: "file content.".as_bytes().to_vec(),
             open_options: None,
         };
@@ -409,7 +1017,8 @@ mod tests {
         assert_eq!(fs::read_to_string(tmp_path.join(&file_name)).unwrap(), "file content.");
 
         let file_node = FileNode::File {
-            name: file_name.clone(),
+            name: file_name.clone().into(),
+            mode: None,
             content: " appended content".as_bytes().to_vec(),
             open_options: Some({
                 let mut options = OpenOptions::new();
@@ -432,40 +1041,49 @@ mod tests {
         let folder_root_name = Uuid::new_v4().to_string();
 
         let root_dir = FileNode::Dir {
-            name: folder_root_name.clone(),
+            name: folder_root_name.clone().into(),
+            mode: None,
             sub: vec![
                 FileNode::File {
-                    name: "fr1".to_string(),
+                    name: "fr1".into(),
+                    mode: None,
                     content: Vec::new(),
                     open_options: None,
                 },
                 FileNode::File {
-                    name: "fr2".to_string(),
+                    name: "fr2".into(),
+                    mode: None,
                     content: "file content fr2".as_bytes().to_vec(),
                     open_options: None,
                 },
                 FileNode::Dir {
-                    name: "d1".to_string(),
+                    name: "d1".into(),
+                    mode: None,
                     sub: vec![
                         FileNode::File {
-                            name: "f1".to_string(),
+                            name: "f1".into(),
+                            mode: None,
                             content: "file content 1".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f2".to_string(),
+                            name: "f2".into(),
+                            mode: None,
                             content: "file content 2".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f3".to_string(),
+                            name: "f3".into(),
+                            mode: None,
                             content: "file content 3".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::Dir {
-                            name: "d11".to_string(),
+                            name: "d11".into(),
+                            mode: None,
                             sub: vec![FileNode::File {
-                                name: "f11".to_string(),
+                                name: "f11".into(),
+                                mode: None,
                                 content: "file content 11".as_bytes().to_vec(),
                                 open_options: None,
                             }],
@@ -473,7 +1091,8 @@ mod tests {
                     ],
                 },
                 FileNode::Dir {
-                    name: "empty_dir".to_string(),
+                    name: "empty_dir".into(),
+                    mode: None,
                     sub: vec![],
                 },
             ],
@@ -515,40 +1134,49 @@ mod tests {
         let folder_root_name = Uuid::new_v4().to_string();
 
         let root_dir = FileNode::Dir {
-            name: folder_root_name.clone(),
+            name: folder_root_name.clone().into(),
+            mode: None,
             sub: vec![
                 FileNode::File {
-                    name: "fr1".to_string(),
+                    name: "fr1".into(),
+                    mode: None,
                     content: Vec::new(),
                     open_options: None,
                 },
                 FileNode::File {
-                    name: "fr2".to_string(),
+                    name: "fr2".into(),
+                    mode: None,
                     content: "file content fr2".as_bytes().to_vec(),
                     open_options: None,
                 },
                 FileNode::Dir {
-                    name: "d1".to_string(),
+                    name: "d1".into(),
+                    mode: None,
                     sub: vec![
                         FileNode::File {
-                            name: "f1".to_string(),
+                            name: "f1".into(),
+                            mode: None,
                             content: "file content 1".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f2".to_string(),
+                            name: "f2".into(),
+                            mode: None,
                             content: "file content 2".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f3".to_string(),
+                            name: "f3".into(),
+                            mode: None,
                             content: "file content 3".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::Dir {
-                            name: "d11".to_string(),
+                            name: "d11".into(),
+                            mode: None,
                             sub: vec![FileNode::File {
-                                name: "f11".to_string(),
+                                name: "f11".into(),
+                                mode: None,
                                 content: "file content 11".as_bytes().to_vec(),
                                 open_options: None,
                             }],
@@ -556,7 +1184,8 @@ mod tests {
                     ],
                 },
                 FileNode::Dir {
-                    name: "empty_dir".to_string(),
+                    name: "empty_dir".into(),
+                    mode: None,
                     sub: vec![],
                 },
             ],
@@ -564,7 +1193,7 @@ mod tests {
 
         root_dir.write_to_path(&tmp_path).unwrap();
 
-        let result = FileNode::new_from_path(&tmp_path.join(&folder_root_name).as_path()).unwrap();
+        let result = FileNode::new_from_path(tmp_path.join(&folder_root_name).as_path()).unwrap();
 
         assert!(result == root_dir);
     }
@@ -579,7 +1208,7 @@ mod tests {
         f.write_all("Some data!".as_bytes()).unwrap();
         f.flush().unwrap();
 
-        let file_node = FileNode::new_from_path(&f_path.as_path()).unwrap();
+        let file_node = FileNode::new_from_path(f_path.as_path()).unwrap();
 
         assert!(file_node == FileNode::new_file(&f_name, "Some data!".as_bytes().to_vec()));
 
@@ -592,10 +1221,222 @@ mod tests {
         let f_path = temp_dir().as_path().join(&f_name);
         fs::create_dir(&f_path).unwrap();
 
-        let dir_node = FileNode::new_from_path(&f_path.as_path()).unwrap();
+        let dir_node = FileNode::new_from_path(f_path.as_path()).unwrap();
 
         assert!(dir_node == FileNode::new_dir(&f_name));
 
         fs::remove_dir(&f_path).unwrap();
     }
+
+    fn make_tree() -> FileNode {
+        crate::file_tree! { "root" => {
+            "b.txt" => "b",
+            "a" => { "z" => "z", "c" => { "f" => "f" } },
+            "e" => {},
+        }}
+    }
+
+    #[test]
+    fn get_should_follow_relative_paths() {
+        let mut tree = make_tree();
+
+        assert!(tree.get("a/c/f") == Some(&FileNode::new_file("f", b"f".to_vec())));
+        assert_eq!(tree.get("./a/c").map(FileNode::get_name), Some(OsStr::new("c")));
+        assert_eq!(tree.get("").map(FileNode::get_name), Some(OsStr::new("root")));
+        assert!(tree.get("a/missing").is_none());
+        assert!(tree.get("b.txt/x").is_none());
+        assert!(tree.get("../a").is_none());
+
+        if let Some(FileNode::File { content, .. }) = tree.get_mut("a/z") {
+            *content = b"updated".to_vec();
+        }
+        assert!(tree.get("a/z") == Some(&FileNode::new_file("z", b"updated".to_vec())));
+    }
+
+    #[test]
+    fn insert_at_should_create_parent_dirs() {
+        let mut tree = make_tree();
+
+        tree.insert_at("a/c", FileNode::new_file("g", vec![])).unwrap();
+        tree.insert_at("x/y", FileNode::new_dir("z")).unwrap();
+        tree.insert_at("", FileNode::new_file("top", vec![])).unwrap();
+
+        assert!(tree.get("a/c/g").is_some());
+        assert!(tree.get("x/y/z") == Some(&FileNode::new_dir("z")));
+        assert!(tree.get("top").is_some());
+        assert!(matches!(tree.insert_at("a", FileNode::new_dir("z")), Err(FsTestError::DuplicateName(n)) if n == "z"));
+        assert!(matches!(tree.insert_at("b.txt", FileNode::new_dir("z")), Err(NeedDir)));
+        assert!(matches!(tree.insert_at("/abs", FileNode::new_dir("z")), Err(InvalidPath(_))));
+    }
+
+    #[test]
+    fn remove_should_detach_node() {
+        let mut tree = make_tree();
+
+        assert_eq!(tree.remove("a/c").map(|n| n.walk().count()), Some(1));
+        assert!(tree.get("a/c").is_none());
+        assert!(tree.remove("a/c").is_none());
+        assert!(tree.remove("").is_none());
+    }
+
+    #[test]
+    fn rename_should_change_node_name() {
+        let mut tree = make_tree();
+
+        tree.rename("a/c", "d").unwrap();
+        assert!(tree.get("a/c").is_none());
+        assert!(tree.get("a/d/f").is_some());
+        tree.rename("a/z", "z").unwrap();
+        assert!(matches!(tree.rename("a/z", "d"), Err(FsTestError::DuplicateName(n)) if n == "d"));
+        assert!(matches!(tree.rename("a/missing", "m"), Err(NotFound(p)) if p == Path::new("a/missing")));
+    }
+
+    #[test]
+    fn walk_should_iterate_depth_first_in_sorted_order() {
+        let tree = make_tree();
+
+        let paths = tree.walk().map(|(p, _)| p.to_str().unwrap().to_string()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["a", "a/c", "a/c/f", "a/z", "b.txt", "e"]);
+        assert_eq!(FileNode::new_file("f", vec![]).walk().count(), 0);
+    }
+
+    #[test]
+    fn children_should_be_kept_sorted_by_name() {
+        use crate::fs::TmpTestFolder;
+
+        let names = |node: &FileNode| match node {
+            FileNode::Dir { sub, .. } => sub.iter().map(|n| n.get_name().to_str().unwrap().to_string()).collect::<Vec<_>>(),
+            _ => vec![],
+        };
+        let mut tree = crate::file_tree! { "root" => { "m" => "", "b" => {}, "x" => "" } };
+        assert_eq!(names(&tree), vec!["b", "m", "x"]);
+
+        tree.insert_at("", FileNode::new_file("c", vec![])).unwrap();
+        tree.insert_at("a/z", FileNode::new_file("f", vec![])).unwrap();
+        tree.rename("x", "d").unwrap();
+        assert_eq!(names(&tree), vec!["a", "b", "c", "d", "m"]);
+
+        let folder = TmpTestFolder::new_from_node(&tree).unwrap();
+        let read = FileNode::new_from_path(&folder.get_path().join("root")).unwrap();
+        assert_eq!(names(&read), vec!["a", "b", "c", "d", "m"]);
+        assert_eq!(format!("{:?}", read), format!("{:?}", FileNode::new_from_path(&folder.get_path().join("root")).unwrap()));
+        assert!(read == tree);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_should_round_trip_without_being_followed() {
+        use crate::fs::TmpTestFolder;
+
+        let mut tree = crate::file_tree! { "root" => { "f" => "content", "d" => {} } };
+        tree.insert_at("", FileNode::new_symlink("to_file", "f")).unwrap();
+        tree.insert_at("d", FileNode::new_symlink("to_dir", "..")).unwrap();
+        tree.insert_at("", FileNode::new_symlink("dangling", "missing")).unwrap();
+        let folder = TmpTestFolder::new_from_node(&tree).unwrap();
+
+        let read = FileNode::new_from_path(&folder.get_path().join("root")).unwrap();
+
+        assert!(read == tree);
+        assert!(read.get("to_file") == Some(&FileNode::new_symlink("to_file", "f")));
+        assert!(read.get("d/to_dir/f").is_none());
+        assert!(tree.write_to_path(folder.get_path()).unwrap().modified.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn modes_should_be_preserved_on_write_and_read() {
+        use crate::fs::TmpTestFolder;
+
+        let tree = crate::file_tree! { "root" [mode 0o750] => { "f" [mode 0o640] => "", "g" => "" } };
+        let folder = TmpTestFolder::new_from_node(&tree).unwrap();
+
+        let read = FileNode::new_from_path(&folder.get_path().join("root")).unwrap();
+
+        assert_eq!(read.get_mode(), Some(0o750));
+        assert_eq!(read.get("f").and_then(FileNode::get_mode), Some(0o640));
+        assert!(read.eq_with_modes(&tree));
+        assert!(!read.eq_with_modes(&crate::file_tree! { "root" => { "f" [mode 0o600] => "", "g" => "" } }));
+        assert!(read == crate::file_tree! { "root" => { "f" [mode 0o600] => "", "g" => "" } });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hard_links_should_be_detected_on_read_and_recreated_on_write() {
+        use crate::fs::TmpTestFolder;
+
+        let folder = TmpTestFolder::new_from_node(&crate::file_tree! { "root" => { "b" => { "f" => "shared" }, "c" => "other" } }).unwrap();
+        let root = folder.get_path().join("root");
+        fs::hard_link(root.join("b").join("f"), root.join("a")).unwrap();
+
+        let read = FileNode::new_from_path(&root).unwrap();
+
+        assert!(read.get("a") == Some(&FileNode::new_file("a", b"shared".to_vec())));
+        assert!(read.get("b/f") == Some(&FileNode::new_hard_link("f", "a")));
+
+        let copy = TmpTestFolder::new_from_node(&read).unwrap();
+        let copy_root = copy.get_path().join("root");
+        fs::write(copy_root.join("a"), "updated").unwrap();
+        assert_eq!(fs::read_to_string(copy_root.join("b").join("f")).unwrap(), "updated");
+        assert!(FileNode::new_from_path(&copy_root).unwrap().get("b/f") == Some(&FileNode::new_hard_link("f", "a")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_names_should_round_trip() {
+        use crate::fs::TmpTestFolder;
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"invalid \xff utf-8");
+        let mut tree = FileNode::new_dir("root");
+        tree.insert_at("", FileNode::new_file(name, b"content".to_vec())).unwrap();
+        assert!(matches!(tree.insert_at(Path::new(name).join("nested"), FileNode::new_dir("d")), Err(NeedDir)));
+        tree.insert_at("sub", FileNode::new_dir(name)).unwrap();
+        let folder = TmpTestFolder::new_from_node(&tree).unwrap();
+
+        let read = FileNode::new_from_path(&folder.get_path().join("root")).unwrap();
+
+        assert!(read == tree);
+        assert!(read.get(name) == Some(&FileNode::new_file(name, b"content".to_vec())));
+        assert!(read.get(Path::new("sub").join(name)).is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn special_files_should_be_read_as_special_nodes() {
+        use crate::fs::TmpTestFolder;
+        use std::os::unix::net::UnixListener;
+
+        let folder = TmpTestFolder::new_from_node(&crate::file_tree! { "root" => {} }).unwrap();
+        let root = folder.get_path().join("root");
+        let _listener = UnixListener::bind(root.join("socket")).unwrap();
+
+        let read = FileNode::new_from_path(&root).unwrap();
+
+        assert!(read.get("socket") == Some(&FileNode::new_special("socket", SpecialKind::Socket)));
+        assert!(matches!(
+            FileNode::new_from_path(Path::new("/dev/null")).unwrap(),
+            FileNode::Special {
+                kind: SpecialKind::CharDevice { .. },
+                ..
+            }
+        ));
+        assert!(FileNode::new_special("s", SpecialKind::Socket) != FileNode::new_special("s", SpecialKind::Fifo));
+        assert!(matches!(FileNode::new_special("s", SpecialKind::Socket).write_to_path(&root), Err(FsTestError::IoError(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fifos_should_be_written_and_read() {
+        use crate::fs::TmpTestFolder;
+
+        let mut tree = crate::file_tree! { "root" => { "f" => "" } };
+        tree.insert_at("", FileNode::new_special("pipe", SpecialKind::Fifo)).unwrap();
+        let folder = TmpTestFolder::new_from_node(&tree).unwrap();
+
+        let read = FileNode::new_from_path(&folder.get_path().join("root")).unwrap();
+
+        assert!(read == tree);
+        assert!(read.get("pipe") == Some(&FileNode::new_special("pipe", SpecialKind::Fifo)));
+        assert!(tree.write_to_path(folder.get_path()).unwrap().unchanged.contains(&PathBuf::from("root/pipe")));
+    }
 }
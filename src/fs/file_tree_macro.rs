@@ -0,0 +1,149 @@
+use crate::fs::FileNode;
+
+#[macro_export]
+macro_rules! file_tree {
+    ($name:literal $([$($opt:tt)*])? => $value:tt) => {
+        $crate::__file_tree_node!($name [$($($opt)*)?] $value)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __file_tree_node {
+    ($name:literal [$(mode $mode:expr)?] { $($sub:literal $([$($opt:tt)*])? => $value:tt),* $(,)? }) => {{
+        const _: () = $crate::fs::file_tree_macro::assert_unique_names(&[$($sub),*]);
+        $crate::fs::FileNode::Dir {
+            name: ::std::ffi::OsString::from($name),
+            sub: $crate::fs::file_tree_macro::sorted_by_name(vec![$($crate::__file_tree_node!($sub [$($($opt)*)?] $value)),*]),
+            mode: $crate::__file_tree_mode!($($mode)?),
+        }
+    }};
+    ($name:literal [] $content:expr) => {
+        $crate::fs::FileNode::new_file($name, ::std::convert::AsRef::<[u8]>::as_ref(&$content).to_vec())
+    };
+    ($name:literal [mode $mode:expr] $content:expr) => {
+        $crate::fs::FileNode::new_file_with_mode($name, ::std::convert::AsRef::<[u8]>::as_ref(&$content).to_vec(), $mode)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __file_tree_mode {
+    () => {
+        ::std::option::Option::None
+    };
+    ($mode:expr) => {
+        ::std::option::Option::Some($mode)
+    };
+}
+
+// Evaluated in a const item by `file_tree!` so that duplicate literal names fail the build.
+#[doc(hidden)]
+pub const fn assert_unique_names(names: &[&str]) {
+    let mut i = 0;
+    while i < names.len() {
+        let mut j = i + 1;
+        while j < names.len() {
+            if str_eq(names[i], names[j]) {
+                panic!("file_tree!: duplicate entry name in directory");
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+#[doc(hidden)]
+pub fn sorted_by_name(mut sub: Vec<FileNode>) -> Vec<FileNode> {
+    sub.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+    sub
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::{FileNode, FsTestError, TmpTestFolder};
+    use std::fs;
+
+    #[test]
+    fn file_tree_should_build_nested_nodes() {
+        let content = String::from("from a variable");
+        let tree = file_tree! { "root" => {
+            "a.txt" => "hello",
+            "bin" => b"\x00\x01",
+            "var" => content,
+            "vec" => (vec![1u8, 2]),
+            "sub" => { "empty" => {} },
+        }};
+
+        assert!(
+            tree == FileNode::Dir {
+                name: "root".into(),
+                mode: None,
+                sub: vec![
+                    FileNode::new_file("a.txt", b"hello".to_vec()),
+                    FileNode::new_file("bin", vec![0, 1]),
+                    FileNode::new_file("var", b"from a variable".to_vec()),
+                    FileNode::new_file("vec", vec![1, 2]),
+                    FileNode::Dir {
+                        name: "sub".into(),
+                        mode: None,
+                        sub: vec![FileNode::new_dir("empty")],
+                    },
+                ],
+            }
+        );
+        assert!(file_tree! { "f" => "" } == FileNode::new_file("f", vec![]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_tree_should_write_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tree = file_tree! { "root" => { "run.sh" [mode 0o700] => "#!/bin/sh", "data" [mode 0o600] => "" } };
+        let folder = TmpTestFolder::new_from_node(&tree).unwrap();
+
+        let mode = |name: &str| fs::metadata(folder.get_path().join("root").join(name)).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode("run.sh"), 0o700);
+        assert_eq!(mode("data"), 0o600);
+        assert_eq!(fs::read_to_string(folder.get_path().join("root").join("run.sh")).unwrap(), "#!/bin/sh");
+    }
+
+    #[test]
+    fn write_to_path_should_reject_duplicate_names() {
+        let tree = FileNode::Dir {
+            name: "root".into(),
+            mode: None,
+            sub: vec![FileNode::new_file("a", vec![]), FileNode::new_dir("b"), FileNode::new_dir("a")],
+        };
+        let folder = TmpTestFolder::new().unwrap();
+
+        match folder.write(&tree) {
+            Err(FsTestError::DuplicateName(name)) => assert_eq!(name, "a"),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(!folder.get_path().join("root").exists());
+    }
+
+    #[test]
+    #[should_panic(expected = "file_tree!: duplicate entry name in directory")]
+    fn assert_unique_names_should_panic_on_duplicates() {
+        assert_unique_names(&["a", "b", "a"]);
+    }
+}
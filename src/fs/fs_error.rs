@@ -1,10 +1,18 @@
 use std::error::Error;
+use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub enum FsTestError {
     NeedDir,
     NeedFile,
+    DuplicateName(OsString),
+    NotFound(PathBuf),
+    AlreadyExists(PathBuf),
+    InvalidPath(PathBuf),
+    InvalidPattern(String),
+    ContentUnavailable(PathBuf),
     IoError(std::io::Error),
 }
 
@@ -13,7 +21,13 @@ impl Display for FsTestError {
         match self {
             FsTestError::NeedDir => f.write_str("need directory"),
             FsTestError::NeedFile => f.write_str("need file"),
-            FsTestError::IoError(e) => f.write_fmt(format_args!("IO error occurred : {}", e.to_string())),
+            FsTestError::DuplicateName(name) => f.write_fmt(format_args!("duplicate entry name : {}", name.to_string_lossy())),
+            FsTestError::NotFound(path) => f.write_fmt(format_args!("entry not found : {}", path.display())),
+            FsTestError::AlreadyExists(path) => f.write_fmt(format_args!("entry already exists : {}", path.display())),
+            FsTestError::InvalidPath(path) => f.write_fmt(format_args!("invalid relative path : {}", path.display())),
+            FsTestError::InvalidPattern(pattern) => f.write_fmt(format_args!("invalid pattern : {}", pattern)),
+            FsTestError::ContentUnavailable(path) => f.write_fmt(format_args!("content was not read : {}", path.display())),
+            FsTestError::IoError(e) => f.write_fmt(format_args!("IO error occurred : {}", e)),
         }
     }
 }
@@ -23,6 +37,12 @@ impl Error for FsTestError {
         match self {
             FsTestError::NeedDir => None,
             FsTestError::NeedFile => None,
+            FsTestError::DuplicateName(_) => None,
+            FsTestError::NotFound(_) => None,
+            FsTestError::AlreadyExists(_) => None,
+            FsTestError::InvalidPath(_) => None,
+            FsTestError::InvalidPattern(_) => None,
+            FsTestError::ContentUnavailable(_) => None,
             FsTestError::IoError(e) => Some(e),
         }
     }
@@ -1,7 +1,15 @@
 pub mod file_tree;
+pub mod file_tree_macro;
 pub mod fs_error;
+mod platform;
+pub mod read_options;
 pub mod tmp_files;
+pub mod tree_diff;
+pub mod write_options;
 
-pub use self::file_tree::FileNode;
+pub use self::file_tree::{FileNode, SpecialKind, Walk};
 pub use self::fs_error::FsTestError;
+pub use self::read_options::ReadOptions;
 pub use self::tmp_files::TmpTestFolder;
+pub use self::tree_diff::{ContentDiff, LineChange, NodeKind, TreeChange, TreeDiff};
+pub use self::write_options::{WriteOptions, WritePolicy, WriteReport};
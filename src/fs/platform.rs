@@ -0,0 +1,119 @@
+use crate::fs::SpecialKind;
+use std::fs::{Metadata, OpenOptions};
+use std::io;
+use std::path::Path;
+
+#[cfg(unix)]
+pub(super) fn mode_of(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+pub(super) fn mode_of(_: &Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+pub(super) fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+pub(super) fn set_mode(_: &Path, _: u32) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(super) fn symlink(target: &Path, path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+#[cfg(not(unix))]
+pub(super) fn symlink(_: &Path, _: &Path) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "symlinks are only supported on unix"))
+}
+
+#[cfg(unix)]
+pub(super) fn no_follow(options: &mut OpenOptions) -> &mut OpenOptions {
+    use std::os::unix::fs::OpenOptionsExt;
+    options.custom_flags(libc::O_NOFOLLOW)
+}
+
+#[cfg(not(unix))]
+pub(super) fn no_follow(options: &mut OpenOptions) -> &mut OpenOptions {
+    options
+}
+
+// Identifies a file that has other hard links pointing to it.
+#[cfg(unix)]
+pub(super) fn linked_inode(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    if metadata.nlink() > 1 {
+        Some((metadata.dev(), metadata.ino()))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn linked_inode(_: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(unix)]
+pub(super) fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::symlink_metadata(a), std::fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn same_file(_: &Path, _: &Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+pub(super) fn special_kind(metadata: &Metadata) -> Option<SpecialKind> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+    let file_type = metadata.file_type();
+    if file_type.is_fifo() {
+        Some(SpecialKind::Fifo)
+    } else if file_type.is_socket() {
+        Some(SpecialKind::Socket)
+    } else if file_type.is_block_device() {
+        Some(SpecialKind::BlockDevice { rdev: metadata.rdev() })
+    } else if file_type.is_char_device() {
+        Some(SpecialKind::CharDevice { rdev: metadata.rdev() })
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn special_kind(_: &Metadata) -> Option<SpecialKind> {
+    None
+}
+
+// Only fifos can be created, sockets need a listening process and devices need privileges.
+#[cfg(unix)]
+pub(super) fn create_special(path: &Path, kind: SpecialKind) -> io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+    if kind != SpecialKind::Fifo {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, format!("cannot create {}", kind)));
+    }
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+    if unsafe { libc::mkfifo(path.as_ptr(), 0o644) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+pub(super) fn create_special(_: &Path, kind: SpecialKind) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, format!("cannot create {}", kind)))
+}
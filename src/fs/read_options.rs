@@ -0,0 +1,292 @@
+use crate::fs::FsTestError;
+use glob::{MatchOptions, Pattern};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Default)]
+pub struct ReadOptions {
+    // Patterns follow the `.gitignore` syntax and are matched against paths relative to the read root.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub ignore_files: Vec<OsString>,
+    pub max_depth: Option<usize>,
+    // Files above this size are read as a `FileDigest` holding only their size and content hash.
+    pub max_file_size: Option<u64>,
+    pub follow_symlinks: bool,
+}
+
+impl ReadOptions {
+    pub fn new() -> Self {
+        ReadOptions::default()
+    }
+
+    pub fn include(mut self, pattern: &str) -> Self {
+        self.include.push(pattern.to_string());
+        self
+    }
+
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(pattern.to_string());
+        self
+    }
+
+    pub fn ignore_file<N: AsRef<OsStr>>(mut self, name: N) -> Self {
+        self.ignore_files.push(name.as_ref().to_os_string());
+        self
+    }
+
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    pub fn max_file_size(mut self, size: u64) -> Self {
+        self.max_file_size = Some(size);
+        self
+    }
+
+    pub fn follow_symlinks(mut self) -> Self {
+        self.follow_symlinks = true;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    base: PathBuf,
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    anchored: bool,
+}
+
+impl Rule {
+    fn parse(line: &str, base: &Path) -> Result<Option<Rule>, FsTestError> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+        let pattern = Pattern::new(line.trim_start_matches('/')).map_err(|e| FsTestError::InvalidPattern(format!("{} ({})", line, e)))?;
+        Ok(Some(Rule {
+            base: base.to_path_buf(),
+            pattern,
+            negated,
+            dir_only,
+            anchored: line.contains('/'),
+        }))
+    }
+
+    // Like in `.gitignore` files, a pattern without a slash matches a name at any depth.
+    fn matches(&self, rel_path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let rel_path = match rel_path.strip_prefix(&self.base) {
+            Ok(rel_path) => rel_path,
+            Err(_) => return false,
+        };
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        if self.anchored {
+            self.pattern.matches_path_with(rel_path, options)
+        } else {
+            rel_path.file_name().is_some_and(|name| self.pattern.matches_with(&name.to_string_lossy(), options))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct ReadFilter {
+    include: Vec<Rule>,
+    exclude: Vec<Rule>,
+    ignored: Vec<Rule>,
+}
+
+impl ReadFilter {
+    pub(super) fn new(options: &ReadOptions) -> Result<Self, FsTestError> {
+        let parse = |patterns: &[String]| -> Result<Vec<Rule>, FsTestError> {
+            let mut rules = vec![];
+            for pattern in patterns {
+                rules.extend(Rule::parse(pattern, Path::new(""))?);
+            }
+            Ok(rules)
+        };
+        Ok(ReadFilter {
+            include: parse(&options.include)?,
+            exclude: parse(&options.exclude)?,
+            ignored: vec![],
+        })
+    }
+
+    pub(super) fn with_ignore_files(&self, options: &ReadOptions, dir: &Path, rel_dir: &Path) -> Result<Self, FsTestError> {
+        let mut filter = self.clone();
+        for name in options.ignore_files.iter() {
+            let path = dir.join(name);
+            if path.is_file() {
+                for line in fs::read_to_string(path)?.lines() {
+                    filter.ignored.extend(Rule::parse(line, rel_dir)?);
+                }
+            }
+        }
+        Ok(filter)
+    }
+
+    // Directories are never filtered out by include patterns, only by exclude patterns and ignore files.
+    pub(super) fn accepts(&self, rel_path: &Path, is_dir: bool) -> bool {
+        if last_match(&self.exclude, rel_path, is_dir).is_some_and(|r| !r.negated) {
+            return false;
+        }
+        if last_match(&self.ignored, rel_path, is_dir).is_some_and(|r| !r.negated) {
+            return false;
+        }
+        is_dir || self.includes(rel_path, is_dir)
+    }
+
+    // Without a matching include pattern, a path is only kept when there are no positive include patterns.
+    pub(super) fn includes(&self, rel_path: &Path, is_dir: bool) -> bool {
+        match last_match(&self.include, rel_path, is_dir) {
+            Some(rule) => !rule.negated,
+            None => self.include.iter().all(|r| r.negated),
+        }
+    }
+}
+
+// As in ignore files, the last matching rule wins so that `!` patterns can override earlier ones.
+fn last_match<'r>(rules: &'r [Rule], rel_path: &Path, is_dir: bool) -> Option<&'r Rule> {
+    rules.iter().rev().find(|r| r.matches(rel_path, is_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_tree;
+    use crate::fs::{FileNode, TmpTestFolder};
+
+    fn folder() -> TmpTestFolder {
+        TmpTestFolder::new_from_node(&file_tree! { "root" => {
+            ".gitignore" => "*.log\n!keep.log\n/build/\n",
+            "app.log" => "log",
+            "keep.log" => "kept",
+            "main.rs" => "fn main() {}",
+            "big.bin" => "0123456789",
+            "build" => { "out" => "" },
+            "src" => { "lib.rs" => "", "build" => { "gen.rs" => "" }, "deep" => { "deeper" => { "f.rs" => "" } } },
+            "target" => { "debug" => { "app" => "" } },
+        }})
+        .unwrap()
+    }
+
+    fn read(folder: &TmpTestFolder, options: ReadOptions) -> Vec<String> {
+        let node = FileNode::new_from_path_with(&folder.get_path().join("root"), &options).unwrap();
+        node.walk().map(|(p, _)| p.to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn read_should_exclude_patterns() {
+        let folder = folder();
+
+        let paths = read(&folder, ReadOptions::new().exclude("target/").exclude("*.log").exclude("src/deep"));
+
+        assert_eq!(
+            paths,
+            vec![".gitignore", "big.bin", "build", "build/out", "main.rs", "src", "src/build", "src/build/gen.rs", "src/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn read_should_keep_included_files_only() {
+        let folder = folder();
+
+        let paths = read(&folder, ReadOptions::new().include("*.rs").exclude("target"));
+
+        assert_eq!(
+            paths,
+            vec!["main.rs", "src", "src/build", "src/build/gen.rs", "src/deep", "src/deep/deeper", "src/deep/deeper/f.rs", "src/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn read_should_honor_negated_patterns() {
+        let folder = folder();
+
+        let paths = read(&folder, ReadOptions::new().exclude("*.log").exclude("!keep.log").exclude("src").exclude("target"));
+        assert_eq!(paths, vec![".gitignore", "big.bin", "build", "build/out", "keep.log", "main.rs"]);
+
+        let paths = read(&folder, ReadOptions::new().include("*.rs").include("!lib.rs").exclude("src/deep").exclude("target"));
+        assert_eq!(paths, vec!["main.rs", "src", "src/build", "src/build/gen.rs"]);
+    }
+
+    #[test]
+    fn read_should_honor_ignore_files() {
+        let folder = folder();
+
+        let paths = read(&folder, ReadOptions::new().ignore_file(".gitignore").max_depth(2));
+
+        assert_eq!(
+            paths,
+            vec![".gitignore", "big.bin", "keep.log", "main.rs", "src", "src/build", "src/deep", "src/lib.rs", "target", "target/debug"]
+        );
+    }
+
+    #[test]
+    fn read_should_store_digest_of_large_files() {
+        let folder = folder();
+
+        let node = FileNode::new_from_path_with(&folder.get_path().join("root"), &ReadOptions::new().max_file_size(9)).unwrap();
+
+        assert!(matches!(node.get("big.bin"), Some(FileNode::FileDigest { size: 10, .. })));
+        assert!(node.get("big.bin") == Some(&FileNode::new_file("big.bin", b"0123456789".to_vec())));
+        assert!(node.get("big.bin") != Some(&FileNode::new_file("big.bin", b"0123456780".to_vec())));
+        assert!(matches!(node.get("keep.log"), Some(FileNode::File { .. })));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_should_follow_symlinks_when_requested() {
+        let folder = folder();
+        let root = folder.get_path().join("root");
+        std::os::unix::fs::symlink("src/deep", root.join("link")).unwrap();
+        std::os::unix::fs::symlink("..", root.join("src").join("parent")).unwrap();
+
+        let node = FileNode::new_from_path_with(&root, &ReadOptions::new().follow_symlinks()).unwrap();
+
+        assert!(node.get("link/deeper/f.rs").is_some());
+        assert!(node.get("src/parent") == Some(&FileNode::new_symlink("parent", "..")));
+        assert!(FileNode::new_from_path(&root).unwrap().get("link") == Some(&FileNode::new_symlink("link", "src/deep")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_should_follow_a_symlinked_root_by_default() {
+        let folder = folder();
+        std::os::unix::fs::symlink("root/src", folder.get_path().join("link")).unwrap();
+        std::os::unix::fs::symlink("src", folder.get_path().join("root").join("src_link")).unwrap();
+
+        let node = FileNode::new_from_path_with(&folder.get_path().join("link"), &ReadOptions::new()).unwrap();
+
+        assert!(node.get("lib.rs").is_some());
+        assert!(node.get("deep/deeper/f.rs").is_some());
+        let root = FileNode::new_from_path(&folder.get_path().join("root")).unwrap();
+        assert!(root.get("src_link") == Some(&FileNode::new_symlink("src_link", "src")));
+    }
+
+    #[test]
+    fn read_should_reject_invalid_patterns() {
+        let folder = folder();
+
+        let result = FileNode::new_from_path_with(folder.get_path(), &ReadOptions::new().exclude("a/***"));
+
+        assert!(matches!(result, Err(FsTestError::InvalidPattern(_))));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::fs::file_tree::FileNode;
 use crate::fs::fs_error::FsTestError;
+use crate::fs::read_options::ReadOptions;
+use crate::fs::write_options::{WriteOptions, WriteReport};
 use std::env::temp_dir;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -27,14 +29,22 @@ impl TmpTestFolder {
         Ok(r)
     }
 
-    pub fn write(&self, node: &FileNode) -> Result<(), FsTestError> {
+    pub fn write(&self, node: &FileNode) -> Result<WriteReport, FsTestError> {
         node.write_to_path(&self.path)
     }
 
+    pub fn write_with(&self, node: &FileNode, options: &WriteOptions) -> Result<WriteReport, FsTestError> {
+        node.write_to_path_with(&self.path, options)
+    }
+
     pub fn read(&self) -> Result<FileNode, FsTestError> {
         FileNode::new_from_path(self.path.as_path())
     }
 
+    pub fn read_with(&self, options: &ReadOptions) -> Result<FileNode, FsTestError> {
+        FileNode::new_from_path_with(self.path.as_path(), options)
+    }
+
     pub fn get_path(&self) -> &Path {
         &self.path
     }
@@ -102,7 +112,8 @@ mod tests {
 
             test_folder
                 .write(&FileNode::File {
-                    name: "fr1".to_string(),
+                    name: "fr1".into(),
+                    mode: None,
                     content: Vec::new(),
                     open_options: None,
                 })
@@ -110,7 +121,8 @@ mod tests {
 
             test_folder
                 .write(&FileNode::File {
-                    name: "fr2".to_string(),
+                    name: "fr2".into(),
+                    mode: None,
                     content: "file content fr2".as_bytes().to_vec(),
                     open_options: None,
                 })
@@ -118,34 +130,41 @@ mod tests {
 
             test_folder
                 .write(&FileNode::Dir {
-                    name: "empty_dir".to_string(),
+                    name: "empty_dir".into(),
+                    mode: None,
                     sub: vec![],
                 })
                 .unwrap();
 
             test_folder
                 .write(&FileNode::Dir {
-                    name: "d1".to_string(),
+                    name: "d1".into(),
+                    mode: None,
                     sub: vec![
                         FileNode::File {
-                            name: "f1".to_string(),
+                            name: "f1".into(),
+                            mode: None,
                             content: "file content 1".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f2".to_string(),
+                            name: "f2".into(),
+                            mode: None,
                             content: "file content 2".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f3".to_string(),
+                            name: "f3".into(),
+                            mode: None,
                             content: "file content 3".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::Dir {
-                            name: "d11".to_string(),
+                            name: "d11".into(),
+                            mode: None,
                             sub: vec![FileNode::File {
-                                name: "f11".to_string(),
+                                name: "f11".into(),
+                                mode: None,
                                 content: "file content 11".as_bytes().to_vec(),
                                 open_options: None,
                             }],
@@ -193,7 +212,8 @@ mod tests {
 
             test_folder
                 .write(&FileNode::File {
-                    name: "fr1".to_string(),
+                    name: "fr1".into(),
+                    mode: None,
                     content: Vec::new(),
                     open_options: None,
                 })
@@ -201,7 +221,8 @@ mod tests {
 
             test_folder
                 .write(&FileNode::File {
-                    name: "fr2".to_string(),
+                    name: "fr2".into(),
+                    mode: None,
                     content: "file content fr2".as_bytes().to_vec(),
                     open_options: None,
                 })
@@ -209,34 +230,41 @@ mod tests {
 
             test_folder
                 .write(&FileNode::Dir {
-                    name: "empty_dir".to_string(),
+                    name: "empty_dir".into(),
+                    mode: None,
                     sub: vec![],
                 })
                 .unwrap();
 
             test_folder
                 .write(&FileNode::Dir {
-                    name: "d1".to_string(),
+                    name: "d1".into(),
+                    mode: None,
                     sub: vec![
                         FileNode::File {
-                            name: "f1".to_string(),
+                            name: "f1".into(),
+                            mode: None,
                             content: "file content 1".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f2".to_string(),
+                            name: "f2".into(),
+                            mode: None,
                             content: "file content 2".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::File {
-                            name: "f3".to_string(),
+                            name: "f3".into(),
+                            mode: None,
                             content: "file content 3".as_bytes().to_vec(),
                             open_options: None,
                         },
                         FileNode::Dir {
-                            name: "d11".to_string(),
+                            name: "d11".into(),
+                            mode: None,
                             sub: vec![FileNode::File {
-                                name: "f11".to_string(),
+                                name: "f11".into(),
+                                mode: None,
                                 content: "file content 11".as_bytes().to_vec(),
                                 open_options: None,
                             }],
@@ -247,43 +275,52 @@ mod tests {
 
             let tmp_node = test_folder.read().unwrap();
 
-            assert_eq!(
+            assert!(
                 tmp_node
                     == FileNode::Dir {
-                        name: test_folder.get_path().file_name().unwrap().to_str().unwrap().to_string(),
+                        name: test_folder.get_path().file_name().unwrap().to_os_string(),
+                        mode: None,
                         sub: vec![
                             FileNode::File {
-                                name: "fr1".to_string(),
+                                name: "fr1".into(),
+                                mode: None,
                                 content: Vec::new(),
                                 open_options: None,
                             },
                             FileNode::File {
-                                name: "fr2".to_string(),
+                                name: "fr2".into(),
+                                mode: None,
                                 content: "file content fr2".as_bytes().to_vec(),
                                 open_options: None,
                             },
                             FileNode::Dir {
-                                name: "d1".to_string(),
+                                name: "d1".into(),
+                                mode: None,
                                 sub: vec![
                                     FileNode::File {
-                                        name: "f1".to_string(),
+                                        name: "f1".into(),
+                                        mode: None,
                                         content: "file content 1".as_bytes().to_vec(),
                                         open_options: None,
                                     },
                                     FileNode::File {
-                                        name: "f2".to_string(),
+                                        name: "f2".into(),
+                                        mode: None,
                                         content: "file content 2".as_bytes().to_vec(),
                                         open_options: None,
                                     },
                                     FileNode::File {
-                                        name: "f3".to_string(),
+                                        name: "f3".into(),
+                                        mode: None,
                                         content: "file content 3".as_bytes().to_vec(),
                                         open_options: None,
                                     },
                                     FileNode::Dir {
-                                        name: "d11".to_string(),
+                                        name: "d11".into(),
+                                        mode: None,
                                         sub: vec![FileNode::File {
-                                            name: "f11".to_string(),
+                                            name: "f11".into(),
+                                            mode: None,
                                             content: "file content 11".as_bytes().to_vec(),
                                             open_options: None,
                                         }],
@@ -291,12 +328,12 @@ mod tests {
                                 ],
                             },
                             FileNode::Dir {
-                                name: "empty_dir".to_string(),
+                                name: "empty_dir".into(),
+                                mode: None,
                                 sub: vec![],
                             },
                         ],
-                    },
-                true
+                    }
             )
         }
 
@@ -0,0 +1,439 @@
+use crate::fs::{FileNode, SpecialKind};
+use std::collections::BTreeSet;
+use std::ffi::OsStr;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeKind {
+    Dir,
+    File,
+    Symlink,
+    HardLink,
+    Special(SpecialKind),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineChange {
+    Removed { line: usize, text: String },
+    Added { line: usize, text: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentDiff {
+    Text(Vec<LineChange>),
+    // The changed lines were too many to be compared line by line, `first_difference` is a line number.
+    LargeText { lines: usize, other_lines: usize, first_difference: usize },
+    Binary { size: usize, other_size: usize, first_difference: usize },
+    // Both texts have the same lines and only differ by line endings, `first_difference` is a byte offset.
+    LineEndings { first_difference: usize },
+    // At least one side is a `FileNode::FileDigest`, so only sizes and hashes could be compared.
+    Digest { size: u64, other_size: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeChange {
+    Added { path: PathBuf, kind: NodeKind },
+    Removed { path: PathBuf, kind: NodeKind },
+    TypeChanged { path: PathBuf, kind: NodeKind, other_kind: NodeKind },
+    Modified { path: PathBuf, content: ContentDiff },
+    TargetChanged { path: PathBuf, target: PathBuf, other_target: PathBuf },
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TreeDiff {
+    pub changes: Vec<TreeChange>,
+}
+
+impl TreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl FileNode {
+    pub fn diff(&self, other: &FileNode) -> TreeDiff {
+        let mut diff = TreeDiff::default();
+        diff_nodes(self, other, Path::new(""), &mut diff.changes);
+        diff
+    }
+
+    pub(crate) fn kind(&self) -> NodeKind {
+        match self {
+            FileNode::Dir { .. } => NodeKind::Dir,
+            FileNode::File { .. } | FileNode::FileDigest { .. } => NodeKind::File,
+            FileNode::Symlink { .. } => NodeKind::Symlink,
+            FileNode::HardLink { .. } => NodeKind::HardLink,
+            FileNode::Special { kind, .. } => NodeKind::Special(*kind),
+        }
+    }
+}
+
+fn diff_nodes(node: &FileNode, other: &FileNode, path: &Path, changes: &mut Vec<TreeChange>) {
+    match (node, other) {
+        (FileNode::Dir { sub, .. }, FileNode::Dir { sub: o_sub, .. }) => {
+            let names: BTreeSet<&OsStr> = sub.iter().chain(o_sub.iter()).map(|n| n.get_name()).collect();
+            for name in names {
+                let nodes = sub.iter().filter(|n| n.get_name() == name).collect::<Vec<_>>();
+                let o_nodes = o_sub.iter().filter(|n| n.get_name() == name).collect::<Vec<_>>();
+                let sub_path = path.join(name);
+                for i in 0..nodes.len().max(o_nodes.len()) {
+                    match (nodes.get(i), o_nodes.get(i)) {
+                        (Some(n), Some(o)) => diff_nodes(n, o, &sub_path, changes),
+                        (Some(n), None) => changes.push(TreeChange::Removed {
+                            path: sub_path.clone(),
+                            kind: n.kind(),
+                        }),
+                        (None, Some(o)) => changes.push(TreeChange::Added {
+                            path: sub_path.clone(),
+                            kind: o.kind(),
+                        }),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+        (FileNode::File { content, .. }, FileNode::File { content: o_content, .. }) => {
+            if content != o_content {
+                changes.push(TreeChange::Modified {
+                    path: path.to_path_buf(),
+                    content: content_diff(content, o_content),
+                });
+            }
+        }
+        (FileNode::File { .. } | FileNode::FileDigest { .. }, FileNode::File { .. } | FileNode::FileDigest { .. }) => {
+            if !node.same_content(other) {
+                changes.push(TreeChange::Modified {
+                    path: path.to_path_buf(),
+                    content: ContentDiff::Digest {
+                        size: file_size(node),
+                        other_size: file_size(other),
+                    },
+                });
+            }
+        }
+        (FileNode::Symlink { target, .. }, FileNode::Symlink { target: o_target, .. }) | (FileNode::HardLink { target, .. }, FileNode::HardLink { target: o_target, .. }) => {
+            if target != o_target {
+                changes.push(TreeChange::TargetChanged {
+                    path: path.to_path_buf(),
+                    target: target.clone(),
+                    other_target: o_target.clone(),
+                });
+            }
+        }
+        (FileNode::Special { kind, .. }, FileNode::Special { kind: o_kind, .. }) if kind == o_kind => {}
+        _ => changes.push(TreeChange::TypeChanged {
+            path: path.to_path_buf(),
+            kind: node.kind(),
+            other_kind: other.kind(),
+        }),
+    }
+}
+
+fn file_size(node: &FileNode) -> u64 {
+    match node {
+        FileNode::File { content, .. } => content.len() as u64,
+        FileNode::FileDigest { size, .. } => *size,
+        _ => 0,
+    }
+}
+
+fn as_text(content: &[u8]) -> Option<&str> {
+    std::str::from_utf8(content).ok().filter(|t| !t.contains('\0'))
+}
+
+pub(crate) fn content_diff(content: &[u8], other: &[u8]) -> ContentDiff {
+    match (as_text(content), as_text(other)) {
+        (Some(text), Some(o_text)) => {
+            let (lines, o_lines) = (text.lines().collect::<Vec<_>>(), o_text.lines().collect::<Vec<_>>());
+            let prefix = lines.iter().zip(o_lines.iter()).take_while(|(l, o)| l == o).count();
+            let suffix = lines[prefix..].iter().rev().zip(o_lines[prefix..].iter().rev()).take_while(|(l, o)| l == o).count();
+            let (changed, o_changed) = (&lines[prefix..lines.len() - suffix], &o_lines[prefix..o_lines.len() - suffix]);
+            if (changed.len() + 1).saturating_mul(o_changed.len() + 1) > MAX_LINE_PAIRS {
+                return ContentDiff::LargeText {
+                    lines: lines.len(),
+                    other_lines: o_lines.len(),
+                    first_difference: prefix + 1,
+                };
+            }
+            let changes = line_changes(changed, o_changed, prefix);
+            if changes.is_empty() {
+                return ContentDiff::LineEndings {
+                    first_difference: first_byte_difference(content, other),
+                };
+            }
+            ContentDiff::Text(changes)
+        }
+        _ => ContentDiff::Binary {
+            size: content.len(),
+            other_size: other.len(),
+            first_difference: first_byte_difference(content, other),
+        },
+    }
+}
+
+fn first_byte_difference(content: &[u8], other: &[u8]) -> usize {
+    content.iter().zip(other).position(|(a, b)| a != b).unwrap_or_else(|| content.len().min(other.len()))
+}
+
+// Bounds the memory of the line table below, about 8MB.
+const MAX_LINE_PAIRS: usize = 1 << 20;
+
+// Longest common subsequence of lines; everything outside of it is reported as removed or added.
+fn line_changes(lines: &[&str], o_lines: &[&str], offset: usize) -> Vec<LineChange> {
+    let mut lcs = vec![vec![0usize; o_lines.len() + 1]; lines.len() + 1];
+    for i in (0..lines.len()).rev() {
+        for j in (0..o_lines.len()).rev() {
+            lcs[i][j] = if lines[i] == o_lines[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut changes = vec![];
+    while i < lines.len() || j < o_lines.len() {
+        if i < lines.len() && j < o_lines.len() && lines[i] == o_lines[j] {
+            i += 1;
+            j += 1;
+        } else if j == o_lines.len() || (i < lines.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(LineChange::Removed {
+                line: offset + i + 1,
+                text: lines[i].to_string(),
+            });
+            i += 1;
+        } else {
+            changes.push(LineChange::Added {
+                line: offset + j + 1,
+                text: o_lines[j].to_string(),
+            });
+            j += 1;
+        }
+    }
+    changes
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeKind::Dir => f.write_str("dir"),
+            NodeKind::File => f.write_str("file"),
+            NodeKind::Symlink => f.write_str("symlink"),
+            NodeKind::HardLink => f.write_str("hard link"),
+            NodeKind::Special(kind) => write!(f, "{}", kind),
+        }
+    }
+}
+
+pub(crate) fn display_path(path: &Path) -> String {
+    if path.as_os_str().is_empty() {
+        return ".".to_string();
+    }
+    path.display().to_string()
+}
+
+impl Display for TreeChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TreeChange::Added { path, kind } => write!(f, "added {}: {}", kind, display_path(path)),
+            TreeChange::Removed { path, kind } => write!(f, "removed {}: {}", kind, display_path(path)),
+            TreeChange::TypeChanged { path, kind, other_kind } => write!(f, "type changed: {} ({} != {})", display_path(path), kind, other_kind),
+            TreeChange::Modified {
+                path,
+                content: content @ ContentDiff::Text(_),
+            } => write!(f, "modified: {}\n{}", display_path(path), content),
+            TreeChange::Modified { path, content } => write!(f, "modified: {} ({})", display_path(path), content),
+            TreeChange::TargetChanged { path, target, other_target } => {
+                write!(f, "target changed: {} ({} != {})", display_path(path), target.display(), other_target.display())
+            }
+        }
+    }
+}
+
+impl Display for ContentDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentDiff::Text(lines) => {
+                for (i, line) in lines.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("\n")?;
+                    }
+                    match line {
+                        LineChange::Removed { line, text } => write!(f, "  -{}: {}", line, text)?,
+                        LineChange::Added { line, text } => write!(f, "  +{}: {}", line, text)?,
+                    }
+                }
+                Ok(())
+            }
+            ContentDiff::LargeText { lines, other_lines, first_difference } => write!(f, "text, {} != {} lines, first difference at line {}", lines, other_lines, first_difference),
+            ContentDiff::Binary { size, other_size, first_difference } => {
+                write!(f, "binary, {} != {} bytes, first difference at offset {}", size, other_size, first_difference)
+            }
+            ContentDiff::LineEndings { first_difference } => write!(f, "text, same lines with different line endings, first difference at offset {}", first_difference),
+            ContentDiff::Digest { size, other_size } => write!(f, "content hash differs, {} and {} bytes", size, other_size),
+        }
+    }
+}
+
+impl Display for TreeDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, change) in self.changes.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(name: &str, content: &str) -> FileNode {
+        FileNode::new_file(name, content.as_bytes().to_vec())
+    }
+
+    fn dir(name: &str, sub: Vec<FileNode>) -> FileNode {
+        FileNode::Dir { name: name.into(), sub, mode: None }
+    }
+
+    #[test]
+    fn diff_should_be_empty_for_equal_trees() {
+        let tree = || dir("root", vec![text("b", "1"), dir("d", vec![text("a", "2")])]);
+        let other = dir("other_root", vec![dir("d", vec![text("a", "2")]), text("b", "1")]);
+        assert!(tree().diff(&tree()).is_empty());
+        assert!(tree().diff(&other).is_empty());
+    }
+
+    #[test]
+    fn diff_should_list_changes_by_relative_path() {
+        let tree = dir("root", vec![text("same", "x"), text("removed", ""), dir("d", vec![text("f", "a\nb\nc"), dir("t", vec![])])]);
+        let other = dir("root", vec![text("same", "x"), dir("added", vec![]), dir("d", vec![text("f", "a\nB\nc\nd"), text("t", "")])]);
+        let diff = tree.diff(&other);
+        assert_eq!(
+            diff.changes,
+            vec![
+                TreeChange::Added {
+                    path: PathBuf::from("added"),
+                    kind: NodeKind::Dir
+                },
+                TreeChange::Modified {
+                    path: PathBuf::from("d/f"),
+                    content: ContentDiff::Text(vec![
+                        LineChange::Removed { line: 2, text: "b".to_string() },
+                        LineChange::Added { line: 2, text: "B".to_string() },
+                        LineChange::Added { line: 4, text: "d".to_string() },
+                    ])
+                },
+                TreeChange::TypeChanged {
+                    path: PathBuf::from("d/t"),
+                    kind: NodeKind::Dir,
+                    other_kind: NodeKind::File
+                },
+                TreeChange::Removed {
+                    path: PathBuf::from("removed"),
+                    kind: NodeKind::File
+                },
+            ]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "added dir: added\nmodified: d/f\n  -2: b\n  +2: B\n  +4: d\ntype changed: d/t (dir != file)\nremoved file: removed"
+        );
+    }
+
+    #[test]
+    fn diff_should_bound_line_comparison_of_large_texts() {
+        let lines = (0..200_000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let mut edited = lines.clone();
+        edited[100_000] = "x".to_string();
+        let reversed = lines.iter().rev().cloned().collect::<Vec<_>>();
+        let file = |lines: &[String]| text("f", &lines.join("\n"));
+
+        assert_eq!(
+            dir("root", vec![file(&lines)]).diff(&dir("root", vec![file(&edited)])).changes,
+            vec![TreeChange::Modified {
+                path: PathBuf::from("f"),
+                content: ContentDiff::Text(vec![
+                    LineChange::Removed {
+                        line: 100_001,
+                        text: "100000".to_string()
+                    },
+                    LineChange::Added { line: 100_001, text: "x".to_string() },
+                ])
+            }]
+        );
+        let diff = dir("root", vec![file(&lines)]).diff(&dir("root", vec![file(&reversed)]));
+        assert_eq!(
+            diff.changes,
+            vec![TreeChange::Modified {
+                path: PathBuf::from("f"),
+                content: ContentDiff::LargeText {
+                    lines: 200_000,
+                    other_lines: 200_000,
+                    first_difference: 1
+                }
+            }]
+        );
+        assert_eq!(diff.to_string(), "modified: f (text, 200000 != 200000 lines, first difference at line 1)");
+    }
+
+    #[test]
+    fn diff_should_report_binary_size_and_first_difference() {
+        let tree = dir("root", vec![FileNode::new_file("bin", vec![0, 1, 2, 3])]);
+        let other = dir("root", vec![FileNode::new_file("bin", vec![0, 1, 9, 3, 4])]);
+        let diff = tree.diff(&other);
+        assert_eq!(
+            diff.changes,
+            vec![TreeChange::Modified {
+                path: PathBuf::from("bin"),
+                content: ContentDiff::Binary {
+                    size: 4,
+                    other_size: 5,
+                    first_difference: 2
+                }
+            }]
+        );
+        assert_eq!(diff.to_string(), "modified: bin (binary, 4 != 5 bytes, first difference at offset 2)");
+    }
+
+    #[test]
+    fn diff_should_report_line_ending_differences() {
+        assert_eq!(
+            text("f", "a\nb").diff(&text("f", "a\nb\n")).changes,
+            vec![TreeChange::Modified {
+                path: PathBuf::from(""),
+                content: ContentDiff::LineEndings { first_difference: 3 }
+            }]
+        );
+        assert_eq!(
+            text("f", "a\r\nb\r\n").diff(&text("f", "a\nb\n")).to_string(),
+            "modified: . (text, same lines with different line endings, first difference at offset 1)"
+        );
+    }
+
+    #[test]
+    fn diff_should_compare_root_nodes() {
+        assert_eq!(text("f", "a").diff(&dir("f", vec![])).to_string(), "type changed: . (file != dir)");
+        assert_eq!(text("f", "a").diff(&text("g", "b")).to_string(), "modified: .\n  -1: a\n  +1: b");
+    }
+
+    #[test]
+    fn diff_should_report_link_target_changes() {
+        let tree = dir("root", vec![FileNode::new_symlink("l", "a"), FileNode::new_hard_link("h", "x")]);
+        let other = dir("root", vec![FileNode::new_symlink("l", "b"), FileNode::new_symlink("h", "x")]);
+        assert_eq!(tree.diff(&other).to_string(), "type changed: h (hard link != symlink)\ntarget changed: l (a != b)");
+    }
+
+    #[test]
+    fn diff_should_compare_digests_by_size_and_hash() {
+        let digest = |name: &str, content: &[u8]| FileNode::FileDigest {
+            name: name.into(),
+            size: content.len() as u64,
+            hash: FileNode::content_hash(content),
+            mode: None,
+        };
+        let tree = dir("root", vec![digest("same", b"abc"), digest("changed", b"abc")]);
+        let other = dir("root", vec![text("same", "abc"), text("changed", "abcd")]);
+        assert_eq!(tree.diff(&other).to_string(), "modified: changed (content hash differs, 3 and 4 bytes)");
+    }
+}
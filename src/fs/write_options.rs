@@ -0,0 +1,244 @@
+use std::path::PathBuf;
+
+// Open options of a `FileNode::File` replace the default `Truncate` policy on existing files,
+// `Append` and `Sync` are applied on top of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WritePolicy {
+    #[default]
+    Truncate,
+    FailIfExists,
+    SkipExisting,
+    Append,
+    // Truncates existing files and deletes entries that are not part of the written node.
+    Sync,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WriteOptions {
+    pub policy: WritePolicy,
+    pub dry_run: bool,
+}
+
+impl WriteOptions {
+    pub fn new(policy: WritePolicy) -> Self {
+        WriteOptions { policy, dry_run: false }
+    }
+
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WriteReport {
+    pub created: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub unchanged: Vec<PathBuf>,
+    pub deleted: Vec<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_tree;
+    use crate::fs::{FileNode, FsTestError, TmpTestFolder};
+    use std::fs;
+    use std::fs::OpenOptions;
+
+    fn write(folder: &TmpTestFolder, node: &FileNode, policy: WritePolicy) -> Result<WriteReport, FsTestError> {
+        node.write_to_path_with(folder.get_path(), &WriteOptions::new(policy))
+    }
+
+    fn existing_folder() -> TmpTestFolder {
+        TmpTestFolder::new_from_node(&file_tree! { "root" => {
+            "f" => "long previous content",
+            "extra" => "extra",
+            "extra_dir" => { "g" => "g" },
+            "d" => "file replaced by a dir",
+        }})
+        .unwrap()
+    }
+
+    fn read(folder: &TmpTestFolder, path: &str) -> String {
+        fs::read_to_string(folder.get_path().join("root").join(path)).unwrap()
+    }
+
+    #[test]
+    fn truncate_policy_should_overwrite_whole_content() {
+        let folder = existing_folder();
+
+        write(&folder, &file_tree! { "root" => { "f" => "short" } }, WritePolicy::Truncate).unwrap();
+
+        assert_eq!(read(&folder, "f"), "short");
+        assert_eq!(read(&folder, "extra"), "extra");
+    }
+
+    #[test]
+    fn fail_if_exists_policy_should_fail_on_existing_file() {
+        let folder = existing_folder();
+
+        let result = write(&folder, &file_tree! { "root" => { "created" => "new", "f" => "short" } }, WritePolicy::FailIfExists);
+
+        assert!(matches!(result, Err(FsTestError::AlreadyExists(p)) if p == folder.get_path().join("root").join("f")));
+        assert!(!folder.get_path().join("root").join("created").exists());
+        assert_eq!(read(&folder, "f"), "long previous content");
+    }
+
+    #[test]
+    fn skip_existing_policy_should_keep_existing_files() {
+        let folder = existing_folder();
+
+        write(&folder, &file_tree! { "root" => { "new" => "new", "f" => "short" } }, WritePolicy::SkipExisting).unwrap();
+
+        assert_eq!(read(&folder, "new"), "new");
+        assert_eq!(read(&folder, "f"), "long previous content");
+    }
+
+    #[test]
+    fn append_policy_should_append_to_existing_files() {
+        let folder = existing_folder();
+
+        write(&folder, &file_tree! { "root" => { "new" => "new", "f" => "!" } }, WritePolicy::Append).unwrap();
+
+        assert_eq!(read(&folder, "new"), "new");
+        assert_eq!(read(&folder, "f"), "long previous content!");
+    }
+
+    #[test]
+    fn sync_policy_should_make_disk_match_node() {
+        let folder = existing_folder();
+        let node = file_tree! { "root" => { "f" => "short", "d" => { "h" => "h" } } };
+
+        write(&folder, &node, WritePolicy::Sync).unwrap();
+
+        assert!(folder.read().unwrap().get("root").unwrap().diff(&node).is_empty());
+        assert!(!folder.get_path().join("root").join("extra_dir").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sync_policy_should_replace_symlinks_instead_of_writing_through_them() {
+        let folder = existing_folder();
+        fs::write(folder.get_path().join("outside.txt"), "outside").unwrap();
+        fs::remove_file(folder.get_path().join("root").join("f")).unwrap();
+        std::os::unix::fs::symlink("../outside.txt", folder.get_path().join("root").join("f")).unwrap();
+
+        let report = write(&folder, &file_tree! { "root" => { "f" => "short" } }, WritePolicy::Sync).unwrap();
+
+        assert_eq!(fs::read_to_string(folder.get_path().join("outside.txt")).unwrap(), "outside");
+        assert!(fs::symlink_metadata(folder.get_path().join("root").join("f")).unwrap().is_file());
+        assert_eq!(read(&folder, "f"), "short");
+        assert!(report.deleted.contains(&PathBuf::from("root/f")));
+        assert!(report.created.contains(&PathBuf::from("root/f")));
+    }
+
+    #[cfg(unix)]
+    fn link_outside_dir(folder: &TmpTestFolder) {
+        fs::create_dir(folder.get_path().join("outside")).unwrap();
+        fs::write(folder.get_path().join("outside").join("h"), "outside").unwrap();
+        std::os::unix::fs::symlink("../outside", folder.get_path().join("root").join("link")).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dir_nodes_should_replace_symlinked_directories() {
+        let folder = existing_folder();
+        link_outside_dir(&folder);
+
+        let report = write(&folder, &file_tree! { "root" => { "link" => { "h" => "h" } } }, WritePolicy::Truncate).unwrap();
+
+        assert_eq!(fs::read_to_string(folder.get_path().join("outside").join("h")).unwrap(), "outside");
+        assert!(fs::symlink_metadata(folder.get_path().join("root").join("link")).unwrap().is_dir());
+        assert_eq!(read(&folder, "link/h"), "h");
+        assert_eq!(report.deleted, paths(&["root/link"]));
+        assert_eq!(report.created, paths(&["root/link", "root/link/h"]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dry_run_should_report_replaced_symlinked_directories_like_a_real_run() {
+        let folder = existing_folder();
+        link_outside_dir(&folder);
+        let node = file_tree! { "root" => { "f" => "short", "link" => { "h" => "outside", "i" => "i" } } };
+
+        let dry_run = node.write_to_path_with(folder.get_path(), &WriteOptions::new(WritePolicy::Sync).dry_run()).unwrap();
+        let report = write(&folder, &node, WritePolicy::Sync).unwrap();
+
+        assert_eq!(dry_run, report);
+        assert_eq!(report.created, paths(&["root/link", "root/link/h", "root/link/i"]));
+        assert!(!report.unchanged.contains(&PathBuf::from("root/link")));
+        assert_eq!(fs::read_dir(folder.get_path().join("outside")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn append_and_sync_policies_should_apply_on_top_of_open_options() {
+        let folder = existing_folder();
+        let node = |options: fn(&mut OpenOptions) -> &mut OpenOptions| FileNode::Dir {
+            name: "root".into(),
+            sub: vec![FileNode::File {
+                name: "f".into(),
+                content: b"!".to_vec(),
+                open_options: Some(options(&mut OpenOptions::new()).clone()),
+                mode: None,
+            }],
+            mode: None,
+        };
+
+        write(&folder, &node(|o| o.append(true)), WritePolicy::Truncate).unwrap();
+        assert_eq!(read(&folder, "f"), "long previous content!");
+
+        write(&folder, &node(|o| o.write(true).truncate(true)), WritePolicy::Append).unwrap();
+        assert_eq!(read(&folder, "f"), "long previous content!!");
+
+        write(&folder, &node(|o| o.append(true)), WritePolicy::Sync).unwrap();
+        assert_eq!(read(&folder, "f"), "!");
+    }
+
+    fn paths(paths: &[&str]) -> Vec<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn write_should_report_changes() {
+        let folder = existing_folder();
+        let node = file_tree! { "root" => { "f" => "short", "extra" => "extra", "d" => { "h" => "h" }, "new" => "" } };
+        let expected = WriteReport {
+            created: paths(&["root/d", "root/d/h", "root/new"]),
+            modified: paths(&["root/f"]),
+            unchanged: paths(&["root", "root/extra"]),
+            deleted: paths(&["root/d", "root/extra_dir"]),
+        };
+
+        let dry_run = node.write_to_path_with(folder.get_path(), &WriteOptions::new(WritePolicy::Sync).dry_run()).unwrap();
+        assert_eq!(dry_run, expected);
+        assert_eq!(read(&folder, "f"), "long previous content");
+        assert!(folder.get_path().join("root").join("extra_dir").exists());
+
+        assert_eq!(write(&folder, &node, WritePolicy::Sync).unwrap(), expected);
+        assert_eq!(
+            write(&folder, &node, WritePolicy::Sync).unwrap(),
+            WriteReport {
+                unchanged: paths(&["root", "root/d", "root/d/h", "root/extra", "root/f", "root/new"]),
+                ..WriteReport::default()
+            }
+        );
+    }
+
+    #[test]
+    fn dry_run_should_report_appended_and_skipped_files() {
+        let folder = existing_folder();
+        let node = file_tree! { "root" => { "f" => "!", "extra" => "" } };
+
+        let report = |policy| node.write_to_path_with(folder.get_path(), &WriteOptions::new(policy).dry_run()).unwrap();
+
+        assert_eq!(report(WritePolicy::Append).modified, paths(&["root/f"]));
+        assert_eq!(report(WritePolicy::Append).unchanged, paths(&["root", "root/extra"]));
+        assert_eq!(report(WritePolicy::SkipExisting).unchanged, paths(&["root", "root/extra", "root/f"]));
+        assert!(matches!(
+            node.write_to_path_with(folder.get_path(), &WriteOptions::new(WritePolicy::FailIfExists).dry_run()),
+            Err(FsTestError::AlreadyExists(_))
+        ));
+        assert_eq!(read(&folder, "f"), "long previous content");
+    }
+}